@@ -0,0 +1,225 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use reqwest::{
+    blocking::{Body, RequestBuilder, Response},
+    header::{CONTENT_RANGE, RANGE},
+    StatusCode,
+};
+
+use crate::{
+    artifacts::{
+        proxied_artifact_path, proxied_artifact_url, proxied_listing_paths, ArtifactRepository,
+        ArtifactStream,
+    },
+    client::{check_status, MlflowClient},
+    error::Error,
+    run::ArtifactInfo,
+    schemas::{ListArtifactsRequest, ListArtifactsResponse, ListProxiedArtifactsRequest},
+};
+
+/// Sends the artifacts through the tracking server, either to its artifact proxy or to the legacy endpoints.
+pub struct HttpArtifactRepository {
+    client: MlflowClient,
+    run_id: String,
+    artifact_uri: String,
+    use_proxy: bool,
+}
+
+impl HttpArtifactRepository {
+    /// Uses the artifact proxy if the artifact backend of the client allows it for the artifact URI.
+    pub fn new(client: &MlflowClient, run_id: &str, artifact_uri: &str) -> Result<Self, Error> {
+        let use_proxy = client
+            .get_artifact_backend()
+            .proxied_path(artifact_uri, "")?
+            .is_some();
+
+        Ok(Self {
+            client: client.clone(),
+            run_id: run_id.to_owned(),
+            artifact_uri: artifact_uri.to_owned(),
+            use_proxy,
+        })
+    }
+
+    fn proxied_path(&self, path: &str) -> Result<Option<String>, Error> {
+        if !self.use_proxy {
+            return Ok(None);
+        }
+
+        proxied_artifact_path(&self.artifact_uri, path).map(Some)
+    }
+
+    fn upload(&self, body: Body, artifact_path: &str) -> Result<(), Error> {
+        let client = self.client.blocking_client()?;
+        let request = match self.proxied_path(artifact_path)? {
+            Some(proxied_path) => client.put(proxied_artifact_url(&self.client, &proxied_path)?),
+            None => client
+                .post(self.client.url("ajax-api/2.0/mlflow/upload-artifact"))
+                .query(&[("run_uuid", self.run_id.as_str()), ("path", artifact_path)]),
+        };
+
+        self.client.send(request.body(body))?;
+
+        Ok(())
+    }
+
+    fn get(&self, path: &str) -> Result<RequestBuilder, Error> {
+        let client = self.client.blocking_client()?;
+
+        Ok(match self.proxied_path(path)? {
+            Some(proxied_path) => client.get(proxied_artifact_url(&self.client, &proxied_path)?),
+            None => client
+                .get(self.client.url("get-artifact"))
+                .query(&[("path", path), ("run_uuid", self.run_id.as_str())]),
+        })
+    }
+}
+
+impl ArtifactRepository for HttpArtifactRepository {
+    fn log_artifact_file(&self, path_on_disk: &Path, artifact_path: &str) -> Result<(), Error> {
+        self.upload(File::open(path_on_disk)?.into(), artifact_path)
+    }
+
+    fn log_artifact_bytes(&self, data: Vec<u8>, artifact_path: &str) -> Result<(), Error> {
+        self.upload(data.into(), artifact_path)
+    }
+
+    fn log_artifact_reader(
+        &self,
+        reader: Box<dyn Read + Send>,
+        artifact_path: &str,
+    ) -> Result<(), Error> {
+        self.upload(Body::new(reader), artifact_path)
+    }
+
+    fn list_artifacts(&self, prefix: &str) -> Result<Vec<ArtifactInfo>, Error> {
+        if let Some(proxied_path) = self.proxied_path(prefix)? {
+            let response = self
+                .client
+                .checked_get_request::<ListProxiedArtifactsRequest, ListArtifactsResponse>(
+                    "api/2.0/mlflow-artifacts/artifacts",
+                    &ListProxiedArtifactsRequest { path: proxied_path },
+                )?;
+
+            return Ok(proxied_listing_paths(prefix, response.files));
+        }
+
+        let response = self
+            .client
+            .checked_get_request::<ListArtifactsRequest, ListArtifactsResponse>(
+                "api/2.0/mlflow/artifacts/list",
+                &ListArtifactsRequest {
+                    run_id: self.run_id.clone(),
+                    path: prefix.to_owned(),
+                },
+            )?;
+
+        Ok(response.files)
+    }
+
+    fn open_artifact(&self, path: &str, offset: u64) -> Result<ArtifactStream, Error> {
+        if offset > 0 {
+            let request = self.get(path)?.header(RANGE, format!("bytes={offset}-"));
+            let response = self.client.send_unchecked(request)?;
+
+            // the requested offset is not smaller than the size of the artifact
+            if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+                if unsatisfied_range_size(&response) == Some(offset) {
+                    return Ok(complete_artifact(offset));
+                }
+            } else {
+                let response = check_status(response)?;
+
+                // the server ignored the range and sends the whole artifact
+                if response.status() != StatusCode::PARTIAL_CONTENT {
+                    return Ok(ArtifactStream {
+                        total_bytes: response.content_length(),
+                        reader: Box::new(response),
+                        offset: 0,
+                    });
+                }
+
+                if let Some((start, total_bytes)) = resumed_range(&response, offset) {
+                    return Ok(ArtifactStream {
+                        reader: Box::new(response),
+                        offset: start,
+                        total_bytes,
+                    });
+                }
+
+                // another part cannot be appended, the whole artifact is requested below
+            }
+        }
+
+        let response = self.client.send(self.get(path)?)?;
+
+        // the 416 response had no size, the local file is complete if it has the size of the artifact
+        if offset > 0 && response.content_length() == Some(offset) {
+            return Ok(complete_artifact(offset));
+        }
+
+        Ok(ArtifactStream {
+            total_bytes: response.content_length(),
+            reader: Box::new(response),
+            offset: 0,
+        })
+    }
+
+    fn delete_artifact(&self, path: &str) -> Result<(), Error> {
+        let proxied_path = self.proxied_path(path)?.ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "the artifacts at {} cannot be deleted without the artifact proxy",
+                self.artifact_uri
+            ))
+        })?;
+
+        let request = self
+            .client
+            .blocking_client()?
+            .delete(proxied_artifact_url(&self.client, &proxied_path)?);
+
+        self.client.send(request)?;
+
+        Ok(())
+    }
+}
+
+/// Stream of an artifact that was downloaded completely already.
+fn complete_artifact(size: u64) -> ArtifactStream {
+    ArtifactStream {
+        reader: Box::new(io::empty()),
+        offset: size,
+        total_bytes: Some(size),
+    }
+}
+
+/// Returns the size of the artifact from a 416 response, e.g. `bytes */200`.
+fn unsatisfied_range_size(response: &Response) -> Option<u64> {
+    let content_range = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+
+    content_range.strip_prefix("bytes */")?.parse().ok()
+}
+
+/// Returns the first byte and the total size of a partial response that starts at `offset`.
+/// Returns `None` if the response contains another range.
+fn resumed_range(response: &Response, offset: u64) -> Option<(u64, Option<u64>)> {
+    // e.g. bytes 100-199/200
+    let content_range = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+    let (range, total) = content_range.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _) = range.split_once('-')?;
+
+    if start.parse::<u64>().ok()? != offset {
+        return None;
+    }
+
+    let total_bytes = total
+        .parse()
+        .ok()
+        .or(response.content_length().map(|length| offset + length));
+
+    Some((offset, total_bytes))
+}
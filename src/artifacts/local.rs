@@ -0,0 +1,203 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Component, Path, PathBuf},
+};
+
+use reqwest::Url;
+
+use crate::{
+    artifacts::{ArtifactRepository, ArtifactStream},
+    error::Error,
+    run::ArtifactInfo,
+};
+
+/// Reads and writes the artifact location of a run directly, e.g. on a network mount that is shared with the tracking server.
+pub struct LocalArtifactRepository {
+    root: PathBuf,
+}
+
+impl LocalArtifactRepository {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    /// Returns `None` if the URI is not a `file:` URI.
+    pub fn from_uri(artifact_uri: &str) -> Option<Self> {
+        local_artifact_root(artifact_uri).map(|root| Self { root })
+    }
+
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Rejects paths that would leave the artifact location.
+    fn resolve(&self, artifact_path: &str) -> Result<PathBuf, Error> {
+        let mut path = self.root.clone();
+
+        for component in Path::new(artifact_path.trim_matches('/')).components() {
+            match component {
+                Component::Normal(name) => path.push(name),
+                Component::CurDir => {}
+                _ => {
+                    return Err(Error::InvalidInput(format!(
+                        "the artifact path {artifact_path} leaves the artifact location"
+                    )))
+                }
+            }
+        }
+
+        Ok(path)
+    }
+
+    fn create_file(&self, artifact_path: &str) -> Result<File, Error> {
+        let path = self.resolve(artifact_path)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        Ok(File::create(path)?)
+    }
+}
+
+impl ArtifactRepository for LocalArtifactRepository {
+    fn log_artifact_file(&self, path_on_disk: &Path, artifact_path: &str) -> Result<(), Error> {
+        io::copy(
+            &mut File::open(path_on_disk)?,
+            &mut self.create_file(artifact_path)?,
+        )?;
+
+        Ok(())
+    }
+
+    fn log_artifact_bytes(&self, data: Vec<u8>, artifact_path: &str) -> Result<(), Error> {
+        io::Write::write_all(&mut self.create_file(artifact_path)?, &data)?;
+
+        Ok(())
+    }
+
+    fn log_artifact_reader(
+        &self,
+        mut reader: Box<dyn Read + Send>,
+        artifact_path: &str,
+    ) -> Result<(), Error> {
+        io::copy(&mut reader, &mut self.create_file(artifact_path)?)?;
+
+        Ok(())
+    }
+
+    fn list_artifacts(&self, prefix: &str) -> Result<Vec<ArtifactInfo>, Error> {
+        let prefix = prefix.trim_matches('/');
+        let dir = self.resolve(prefix)?;
+
+        // like the tracking server, files and missing paths have no children
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+
+                ArtifactInfo {
+                    path: if prefix.is_empty() {
+                        name.into_owned()
+                    } else {
+                        format!("{prefix}/{name}")
+                    },
+                    is_dir: entry.path().is_dir(),
+                }
+            })
+            .collect())
+    }
+
+    fn open_artifact(&self, path: &str, offset: u64) -> Result<ArtifactStream, Error> {
+        let mut file = File::open(self.resolve(path)?)?;
+        let size = file.metadata()?.len();
+        let offset = if offset <= size { offset } else { 0 };
+
+        file.seek(SeekFrom::Start(offset))?;
+
+        Ok(ArtifactStream {
+            reader: Box::new(file),
+            offset,
+            total_bytes: Some(size),
+        })
+    }
+
+    fn delete_artifact(&self, path: &str) -> Result<(), Error> {
+        let path = self.resolve(path)?;
+
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the directory of a `file:` artifact URI like `file:///mnt/mlruns/1/<run_id>/artifacts`.
+pub(crate) fn local_artifact_root(artifact_uri: &str) -> Option<PathBuf> {
+    if !artifact_uri.starts_with("file:") {
+        return None;
+    }
+
+    Url::parse(artifact_uri).ok()?.to_file_path().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_stays_inside_the_root() {
+        let repository = LocalArtifactRepository::new(Path::new("/mnt/mlruns/1/run/artifacts"));
+
+        assert_eq!(
+            repository.resolve("model/./MLmodel").unwrap(),
+            Path::new("/mnt/mlruns/1/run/artifacts/model/MLmodel")
+        );
+        assert_eq!(
+            repository.resolve("").unwrap(),
+            Path::new("/mnt/mlruns/1/run/artifacts")
+        );
+        // artifact paths are relative to the root, even with a leading slash
+        assert_eq!(
+            repository.resolve("/etc/passwd").unwrap(),
+            Path::new("/mnt/mlruns/1/run/artifacts/etc/passwd")
+        );
+    }
+
+    #[test]
+    fn resolve_rejects_parent_directories() {
+        let repository = LocalArtifactRepository::new(Path::new("/mnt/mlruns/1/run/artifacts"));
+
+        assert!(repository.resolve("..").is_err());
+        assert!(repository.resolve("../other-run/artifacts/model").is_err());
+        assert!(repository.resolve("model/../../other-run").is_err());
+    }
+
+    #[test]
+    fn local_artifact_root_of_file_uris() {
+        assert_eq!(
+            local_artifact_root("file:///mnt/ml%20runs/1/run/artifacts"),
+            Some(PathBuf::from("/mnt/ml runs/1/run/artifacts"))
+        );
+        assert_eq!(local_artifact_root("/mnt/mlruns/1/run/artifacts"), None);
+        assert_eq!(
+            local_artifact_root("mlflow-artifacts:/1/run/artifacts"),
+            None
+        );
+        assert_eq!(local_artifact_root("s3://bucket/1/run/artifacts"), None);
+    }
+}
@@ -0,0 +1,480 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use glob::Pattern;
+
+use log::error;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, ETAG},
+    Url,
+};
+
+use crate::{
+    client::{MlflowClient, RetryPolicy},
+    error::Error,
+    run::ArtifactInfo,
+    schemas::{
+        AbortMultipartUploadRequest, AbortMultipartUploadResponse, CompleteMultipartUploadRequest,
+        CompleteMultipartUploadResponse, CreateMultipartUploadRequest,
+        CreateMultipartUploadResponse, MultipartUploadPart,
+    },
+};
+
+pub use self::{http::HttpArtifactRepository, local::LocalArtifactRepository};
+
+mod http;
+mod local;
+
+/// Storage of the artifacts of a run. All paths are relative to the artifact root of the run.
+pub trait ArtifactRepository: Send + Sync {
+    fn log_artifact_file(&self, path_on_disk: &Path, artifact_path: &str) -> Result<(), Error>;
+
+    fn log_artifact_bytes(&self, data: Vec<u8>, artifact_path: &str) -> Result<(), Error>;
+
+    fn log_artifact_reader(
+        &self,
+        reader: Box<dyn Read + Send>,
+        artifact_path: &str,
+    ) -> Result<(), Error>;
+
+    /// Lists the direct children of the directory. Files have no children.
+    fn list_artifacts(&self, prefix: &str) -> Result<Vec<ArtifactInfo>, Error>;
+
+    /// Opens the artifact for reading. The stream starts at `offset` if the repository supports it, otherwise at 0.
+    fn open_artifact(&self, path: &str, offset: u64) -> Result<ArtifactStream, Error>;
+
+    /// Deletes the file or directory.
+    fn delete_artifact(&self, path: &str) -> Result<(), Error>;
+}
+
+pub struct ArtifactStream {
+    pub reader: Box<dyn Read + Send>,
+    /// Position of the first byte of the reader in the artifact.
+    pub offset: u64,
+    /// Size of the whole artifact, if it is known.
+    pub total_bytes: Option<u64>,
+}
+
+/// Selects where the artifacts of runs are read from and written to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArtifactBackend {
+    /// Accesses the directory of a `file:` artifact URI directly if it or the directory of its experiment is writable on this machine.
+    /// Otherwise uses the artifact proxy for runs with an `mlflow-artifacts:` artifact URI and the legacy endpoints for all others.
+    #[default]
+    Auto,
+    /// Always accesses the directory of a `file:` artifact URI directly, e.g. on a network mount shared with the tracking server.
+    /// Only the blocking runs support this, async runs use the legacy endpoints instead.
+    Local,
+    /// The documented `api/2.0/mlflow-artifacts/artifacts` endpoints of the artifact proxy.
+    Proxy,
+    /// The `upload-artifact`, `get-artifact` and `artifacts/list` endpoints that are also used by the web UI.
+    /// They work with any artifact store the tracking server can access, but cannot delete artifacts.
+    Legacy,
+}
+
+impl ArtifactBackend {
+    /// Returns the path of the artifact below the artifact proxy, or `None` if the legacy endpoints are used.
+    pub(crate) fn proxied_path(
+        &self,
+        artifact_uri: &str,
+        path: &str,
+    ) -> Result<Option<String>, Error> {
+        match self {
+            Self::Auto => Ok(proxied_artifact_path(artifact_uri, path).ok()),
+            Self::Proxy => proxied_artifact_path(artifact_uri, path).map(Some),
+            Self::Local | Self::Legacy => Ok(None),
+        }
+    }
+}
+
+/// Selects the repository for the artifacts of a run according to the artifact backend of the client.
+pub(crate) fn artifact_repository(
+    client: &MlflowClient,
+    run_id: &str,
+    artifact_uri: &str,
+) -> Result<Box<dyn ArtifactRepository>, Error> {
+    let local_repository = LocalArtifactRepository::from_uri(artifact_uri);
+
+    match (client.get_artifact_backend(), local_repository) {
+        (ArtifactBackend::Local, Some(repository)) => Ok(Box::new(repository)),
+        (ArtifactBackend::Local, None) => Err(Error::InvalidInput(format!(
+            "the artifacts at {artifact_uri} are not stored in a local directory"
+        ))),
+        // the directory might only exist on the machine of the tracking server
+        (ArtifactBackend::Auto, Some(repository))
+            if is_local_artifact_root(repository.get_root()) =>
+        {
+            Ok(Box::new(repository))
+        }
+        _ => Ok(Box::new(HttpArtifactRepository::new(
+            client,
+            run_id,
+            artifact_uri,
+        )?)),
+    }
+}
+
+/// Whether the artifact directory `<experiment>/<run_id>/artifacts` is accessible on this machine.
+/// The tracking server only creates it with the first artifact, so a writable directory of the experiment is enough.
+/// Nothing is created here, the missing directories are created by the first write.
+fn is_local_artifact_root(root: &Path) -> bool {
+    root.ancestors()
+        .take(3)
+        // the file system root exists everywhere
+        .filter(|dir| dir.parent().is_some())
+        .find_map(|dir| fs::metadata(dir).ok())
+        .is_some_and(|metadata| metadata.is_dir() && !metadata.permissions().readonly())
+}
+
+#[derive(Clone, Debug)]
+pub struct MultipartUploadConfig {
+    /// Size of each part in bytes. Storage services usually require at least 5 MiB for all parts but the last.
+    pub chunk_size: u64,
+    /// Applied to each part on its own, independent of the retry policy of the client.
+    pub part_retry_policy: RetryPolicy,
+}
+
+impl Default for MultipartUploadConfig {
+    fn default() -> Self {
+        Self {
+            // same default as the python client
+            chunk_size: 100 * 1024 * 1024,
+            part_retry_policy: RetryPolicy {
+                max_retries: 3,
+                backoff: Duration::from_secs(1),
+            },
+        }
+    }
+}
+
+/// Selects the files of [`crate::run::Run::log_artifacts_dir`] and how they are uploaded.
+#[derive(Clone, Debug)]
+pub struct UploadDirOptions {
+    /// Glob patterns that are matched against the path relative to the directory, e.g. `*.json` or `checkpoints/*`.
+    /// All files are included if this is empty.
+    pub include: Vec<String>,
+    /// Files matching any of these patterns are skipped, even if they are included.
+    pub exclude: Vec<String>,
+    pub max_parallel_uploads: usize,
+}
+
+impl Default for UploadDirOptions {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_parallel_uploads: 1,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UploadSummary {
+    /// Artifact paths of the uploaded files.
+    pub uploaded: Vec<String>,
+    /// Artifact paths of the files that could not be uploaded.
+    pub failed: Vec<(String, Error)>,
+}
+
+impl UploadSummary {
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Includes the bytes of a partial file that was resumed.
+    pub bytes_done: u64,
+    /// Size of the artifact, known if the server sent the `Content-Length` header.
+    pub total_bytes: Option<u64>,
+}
+
+/// Copies the reader into the writer in chunks and reports the progress after each chunk.
+pub(crate) fn copy_with_progress(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    mut progress: DownloadProgress,
+    on_progress: &mut impl FnMut(DownloadProgress),
+) -> Result<u64, Error> {
+    let mut buffer = vec![0; 64 * 1024];
+
+    on_progress(progress);
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+
+        if read == 0 {
+            writer.flush()?;
+            return Ok(progress.bytes_done);
+        }
+
+        writer.write_all(&buffer[..read])?;
+        progress.bytes_done += read as u64;
+        on_progress(progress);
+    }
+}
+
+/// Returns the files below `dir` that match the options together with their relative paths, in a stable order.
+pub(crate) fn collect_files(
+    dir: &Path,
+    options: &UploadDirOptions,
+) -> Result<Vec<(PathBuf, String)>, Error> {
+    let include = parse_patterns(&options.include)?;
+    let exclude = parse_patterns(&options.exclude)?;
+    let mut files = Vec::new();
+
+    collect_files_recursively(dir, "", &mut files)?;
+
+    files.retain(|(_, relative_path)| {
+        (include.is_empty() || include.iter().any(|pattern| pattern.matches(relative_path)))
+            && !exclude.iter().any(|pattern| pattern.matches(relative_path))
+    });
+
+    Ok(files)
+}
+
+fn parse_patterns(patterns: &[String]) -> Result<Vec<Pattern>, Error> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Pattern::new(pattern)
+                .map_err(|e| Error::InvalidInput(format!("invalid glob pattern {pattern}: {e}")))
+        })
+        .collect()
+}
+
+fn collect_files_recursively(
+    dir: &Path,
+    relative_dir: &str,
+    files: &mut Vec<(PathBuf, String)>,
+) -> Result<(), Error> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let relative_path = if relative_dir.is_empty() {
+            name.into_owned()
+        } else {
+            format!("{relative_dir}/{name}")
+        };
+
+        let file_type = entry.file_type()?;
+
+        // symlinks to directories are skipped, they could form a cycle
+        if file_type.is_dir() {
+            collect_files_recursively(&entry.path(), &relative_path, files)?;
+        } else if !(file_type.is_symlink() && entry.path().is_dir()) {
+            files.push((entry.path(), relative_path));
+        }
+    }
+
+    Ok(())
+}
+
+/// Turns an artifact path of a run into a path relative to the root of the artifact proxy of the tracking server.
+/// This requires an artifact URI like `mlflow-artifacts:/<experiment_id>/<run_id>/artifacts`.
+pub(crate) fn proxied_artifact_path(artifact_uri: &str, path: &str) -> Result<String, Error> {
+    let location = artifact_uri
+        .strip_prefix("mlflow-artifacts:")
+        .ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "the artifacts at {artifact_uri} are not served by the tracking server"
+            ))
+        })?;
+
+    // the URI might contain the host, e.g. mlflow-artifacts://host:5000/1/<run_id>/artifacts
+    let location = match location.strip_prefix("//") {
+        Some(location) => location.split_once('/').map_or("", |(_, path)| path),
+        None => location,
+    };
+
+    let root = location.trim_matches('/');
+    let path = path.trim_matches('/');
+
+    Ok(match (root.is_empty(), path.is_empty()) {
+        (true, _) => path.to_owned(),
+        (false, true) => root.to_owned(),
+        (false, false) => format!("{root}/{path}"),
+    })
+}
+
+/// Returns the URL of the artifact at the proxied path with each segment percent-encoded.
+pub(crate) fn proxied_artifact_url(
+    client: &MlflowClient,
+    proxied_path: &str,
+) -> Result<Url, Error> {
+    let base = client.url("api/2.0/mlflow-artifacts/artifacts");
+    let mut url =
+        Url::parse(&base).map_err(|e| Error::InvalidInput(format!("invalid URL {base}: {e}")))?;
+
+    url.path_segments_mut()
+        .map_err(|_| Error::InvalidInput(format!("invalid URL {base}")))?
+        .extend(proxied_path.split('/'));
+
+    Ok(url)
+}
+
+/// Turns the paths of a listing of the artifact proxy, which only contain the file names, into paths of the run.
+pub(crate) fn proxied_listing_paths(
+    prefix: &str,
+    mut files: Vec<ArtifactInfo>,
+) -> Vec<ArtifactInfo> {
+    let prefix = prefix.trim_matches('/');
+
+    for file in &mut files {
+        let name = file.path.rsplit('/').next().unwrap_or(&file.path);
+
+        file.path = if prefix.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{prefix}/{name}")
+        };
+    }
+
+    files
+}
+
+/// Uploads `size` bytes from the reader in parts through the artifact proxy of the tracking server.
+/// The upload is aborted if a part cannot be uploaded, so no incomplete artifact is left behind.
+pub(crate) fn multipart_upload(
+    client: &MlflowClient,
+    proxied_path: &str,
+    mut reader: impl Read,
+    size: u64,
+    config: &MultipartUploadConfig,
+) -> Result<(), Error> {
+    if config.chunk_size == 0 {
+        return Err(Error::InvalidInput(
+            "the chunk size of a multipart upload must not be 0".to_owned(),
+        ));
+    }
+
+    let (directory, file_name) = proxied_path.rsplit_once('/').unwrap_or(("", proxied_path));
+
+    let upload: CreateMultipartUploadResponse = client.checked_post_request(
+        &format!("api/2.0/mlflow-artifacts/mpu/create/{directory}"),
+        &CreateMultipartUploadRequest {
+            path: file_name.to_owned(),
+            num_parts: size.div_ceil(config.chunk_size).max(1),
+        },
+    )?;
+
+    let result = upload_parts(client, &mut reader, &upload, config).and_then(|parts| {
+        client.checked_post_request::<_, CompleteMultipartUploadResponse>(
+            &format!("api/2.0/mlflow-artifacts/mpu/complete/{directory}"),
+            &CompleteMultipartUploadRequest {
+                path: file_name.to_owned(),
+                upload_id: upload.upload_id.clone(),
+                parts,
+            },
+        )
+    });
+
+    if let Err(e) = result {
+        let abort_result = client.checked_post_request::<_, AbortMultipartUploadResponse>(
+            &format!("api/2.0/mlflow-artifacts/mpu/abort/{directory}"),
+            &AbortMultipartUploadRequest {
+                path: file_name.to_owned(),
+                upload_id: upload.upload_id.clone(),
+            },
+        );
+
+        if let Err(abort_error) = abort_result {
+            error!("could not abort the multipart upload of {proxied_path}: {abort_error}");
+        }
+
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+fn upload_parts(
+    client: &MlflowClient,
+    reader: &mut impl Read,
+    upload: &CreateMultipartUploadResponse,
+    config: &MultipartUploadConfig,
+) -> Result<Vec<MultipartUploadPart>, Error> {
+    let mut credentials: Vec<_> = upload.credentials.iter().collect();
+    credentials.sort_by_key(|credential| credential.part_number);
+
+    let mut parts = Vec::with_capacity(credentials.len());
+
+    for credential in credentials {
+        let mut chunk = Vec::new();
+        reader.take(config.chunk_size).read_to_end(&mut chunk)?;
+
+        let mut headers = HeaderMap::new();
+
+        for (name, value) in &credential.headers {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|e| Error::InvalidInput(format!("invalid header name {name}: {e}")))?,
+                HeaderValue::from_str(value).map_err(|e| {
+                    Error::InvalidInput(format!("invalid value for header {name}: {e}"))
+                })?,
+            );
+        }
+
+        // the URL is presigned by the storage service, so the credentials of the tracking server must not be sent
+        let request = client
+            .external_blocking_client()?
+            .put(&credential.url)
+            .headers(headers)
+            .body(chunk);
+        let response = client.send_with_retry_policy(request, &config.part_retry_policy)?;
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .unwrap_or_default();
+
+        parts.push(MultipartUploadPart {
+            part_number: credential.part_number,
+            etag: etag.to_owned(),
+            url: credential.url.clone(),
+        });
+    }
+
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proxied_artifact_path_of_mlflow_artifacts_uris() {
+        let uri = "mlflow-artifacts:/1/run/artifacts";
+
+        assert_eq!(proxied_artifact_path(uri, "").unwrap(), "1/run/artifacts");
+        assert_eq!(
+            proxied_artifact_path(uri, "/model/MLmodel").unwrap(),
+            "1/run/artifacts/model/MLmodel"
+        );
+        assert_eq!(
+            proxied_artifact_path("mlflow-artifacts://host:5000/1/run/artifacts/", "model")
+                .unwrap(),
+            "1/run/artifacts/model"
+        );
+        assert_eq!(
+            proxied_artifact_path("mlflow-artifacts:", "model").unwrap(),
+            "model"
+        );
+    }
+
+    #[test]
+    fn proxied_artifact_path_rejects_other_uris() {
+        assert!(proxied_artifact_path("file:///mnt/mlruns/1/run/artifacts", "model").is_err());
+        assert!(proxied_artifact_path("/mnt/mlruns/1/run/artifacts", "model").is_err());
+        assert!(proxied_artifact_path("s3://bucket/1/run/artifacts", "model").is_err());
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    panic::RefUnwindSafe,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+    },
+    thread::{self, JoinHandle},
+};
+
+use log::error;
+use thiserror::Error;
+
+use crate::{
+    client::MlflowClient,
+    run::{log_batch_request, Metric, RunParameters, RunTag, MAX_ENTITIES_PER_BATCH},
+};
+
+/// Decides what happens when an entry is logged while the queue is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FullQueuePolicy {
+    /// Blocks the logging thread until the worker made room in the queue.
+    Block,
+    /// Drops the new entry.
+    /// The number of dropped entries is returned as an error by the next flush.
+    Drop,
+}
+
+#[derive(Clone, Debug)]
+pub struct AsyncLoggingConfig {
+    pub capacity: usize,
+    pub full_queue_policy: FullQueuePolicy,
+}
+
+impl Default for AsyncLoggingConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            full_queue_policy: FullQueuePolicy::Block,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[error(
+    "asynchronous logging lost data: {} failed request(s), {dropped_entries} dropped entries{}",
+    errors.len(),
+    errors.iter().map(|e| format!("; {e}")).collect::<String>()
+)]
+pub struct AsyncLoggingError {
+    pub errors: Vec<crate::Error>,
+    /// Entries that were not logged because the queue was full, see [`FullQueuePolicy::Drop`].
+    pub dropped_entries: usize,
+}
+
+pub(crate) enum QueueEntry {
+    Metric(Metric),
+    Parameter(RunParameters),
+    Tag(RunTag),
+}
+
+enum Message {
+    Entry(QueueEntry),
+    Flush(mpsc::Sender<Vec<crate::Error>>),
+}
+
+pub(crate) struct LogQueue {
+    sender: Option<SyncSender<Message>>,
+    worker: Option<JoinHandle<()>>,
+    full_queue_policy: FullQueuePolicy,
+    dropped_entries: AtomicUsize,
+}
+
+impl LogQueue {
+    pub(crate) fn new(client: &MlflowClient, run_id: &str, config: AsyncLoggingConfig) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(config.capacity);
+        let client = client.clone();
+        let run_id = run_id.to_owned();
+
+        let worker = thread::spawn(move || worker_loop(&client, &run_id, receiver));
+
+        Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            full_queue_policy: config.full_queue_policy,
+            dropped_entries: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn push(&self, entry: QueueEntry) {
+        let sender = self
+            .sender
+            .as_ref()
+            .expect("sender exists until the queue is dropped");
+
+        let sent = match self.full_queue_policy {
+            FullQueuePolicy::Block => sender.send(Message::Entry(entry)).is_ok(),
+            FullQueuePolicy::Drop => match sender.try_send(Message::Entry(entry)) {
+                Ok(_) => true,
+                Err(TrySendError::Full(_)) => {
+                    self.dropped_entries.fetch_add(1, Ordering::Relaxed);
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            },
+        };
+
+        if !sent {
+            error!("the asynchronous logging worker stopped, the entry was not logged");
+        }
+    }
+
+    /// Waits until all queued entries were sent.
+    /// Returns the errors and dropped entries since the last flush.
+    pub(crate) fn flush(&self) -> Result<(), AsyncLoggingError> {
+        let sender = self
+            .sender
+            .as_ref()
+            .expect("sender exists until the queue is dropped");
+        let (reply_sender, reply_receiver) = mpsc::channel();
+
+        let errors = match sender.send(Message::Flush(reply_sender)) {
+            Ok(_) => reply_receiver
+                .recv()
+                .unwrap_or_else(|_| vec![worker_stopped_error()]),
+            Err(_) => vec![worker_stopped_error()],
+        };
+
+        // entries can be dropped until the flush message was queued
+        let dropped_entries = self.dropped_entries.swap(0, Ordering::Relaxed);
+
+        if errors.is_empty() && dropped_entries == 0 {
+            Ok(())
+        } else {
+            Err(AsyncLoggingError {
+                errors,
+                dropped_entries,
+            })
+        }
+    }
+}
+
+// the state of the worker thread is only reachable through the channel,
+// so a panic of the caller cannot leave it broken
+impl RefUnwindSafe for LogQueue {}
+
+impl Drop for LogQueue {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            error!("{e}");
+        }
+
+        // dropping the sender stops the worker
+        self.sender.take();
+
+        if let Some(worker) = self.worker.take() {
+            if worker.join().is_err() {
+                error!("the asynchronous logging worker panicked");
+            }
+        }
+    }
+}
+
+fn worker_stopped_error() -> crate::Error {
+    crate::Error::Other("the asynchronous logging worker stopped unexpectedly".into())
+}
+
+fn worker_loop(client: &MlflowClient, run_id: &str, receiver: Receiver<Message>) {
+    let mut errors = Vec::new();
+    let mut metrics = Vec::new();
+    let mut params: Vec<RunParameters> = Vec::new();
+    let mut tags = Vec::new();
+
+    while let Ok(message) = receiver.recv() {
+        let mut next_message = Some(message);
+        let mut flush_reply = None;
+
+        // coalesce everything that is already waiting in the queue into one batch
+        while let Some(message) = next_message.take() {
+            match message {
+                Message::Entry(QueueEntry::Metric(metric)) => metrics.push(metric),
+                Message::Entry(QueueEntry::Parameter(param)) => {
+                    match params.iter().find(|queued| queued.key == param.key) {
+                        // the server rejects batches that contain a parameter twice,
+                        // even with the same value
+                        Some(queued) if queued.value == param.value => {}
+                        // sent alone like in blocking mode, so only this parameter fails
+                        Some(_) => {
+                            send_batch(
+                                client,
+                                run_id,
+                                &mut metrics,
+                                &mut params,
+                                &mut tags,
+                                &mut errors,
+                            );
+                            params.push(param);
+                            send_batch(
+                                client,
+                                run_id,
+                                &mut metrics,
+                                &mut params,
+                                &mut tags,
+                                &mut errors,
+                            );
+                        }
+                        None => params.push(param),
+                    }
+                }
+                Message::Entry(QueueEntry::Tag(tag)) => tags.push(tag),
+                Message::Flush(reply) => {
+                    flush_reply = Some(reply);
+                    break;
+                }
+            }
+
+            // a producer that keeps the queue filled must not delay the requests indefinitely
+            if metrics.len() + params.len() + tags.len() >= MAX_ENTITIES_PER_BATCH {
+                send_batch(
+                    client,
+                    run_id,
+                    &mut metrics,
+                    &mut params,
+                    &mut tags,
+                    &mut errors,
+                );
+            }
+
+            next_message = receiver.try_recv().ok();
+        }
+
+        send_batch(
+            client,
+            run_id,
+            &mut metrics,
+            &mut params,
+            &mut tags,
+            &mut errors,
+        );
+
+        if let Some(reply) = flush_reply {
+            let _ = reply.send(std::mem::take(&mut errors));
+        }
+    }
+}
+
+fn send_batch(
+    client: &MlflowClient,
+    run_id: &str,
+    metrics: &mut Vec<Metric>,
+    params: &mut Vec<RunParameters>,
+    tags: &mut Vec<RunTag>,
+    errors: &mut Vec<crate::Error>,
+) {
+    if metrics.is_empty() && params.is_empty() && tags.is_empty() {
+        return;
+    }
+
+    if let Err(e) = log_batch_request(client, run_id, metrics, params, tags) {
+        errors.push(e);
+    }
+
+    metrics.clear();
+    params.clear();
+    tags.clear();
+}
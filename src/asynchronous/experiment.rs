@@ -0,0 +1,231 @@
+use std::time::SystemTime;
+
+use log::error;
+use serde::Deserialize;
+
+use crate::{
+    asynchronous::run::Run,
+    client::MlflowClient,
+    error::Error,
+    experiment::{add_source_tags, check_repo_is_supported, ExperimentTag},
+    git_utils::{create_diff, is_repo_clean},
+    run::RunTag,
+    schemas::{
+        CreateExperimentRequest, CreateExperimentResponse, CreateRunRequest, CreateRunResponse,
+        GetExperimentByNameRequest, GetExperimentRequest, GetExperimentResponse,
+    },
+};
+
+#[derive(Deserialize, Default)]
+pub struct Experiment {
+    #[serde(skip)]
+    client: MlflowClient,
+    experiment_id: String,
+    name: String,
+    artifact_location: String,
+    lifecycle_stage: String,
+    last_update_time: u64,
+    creation_time: u64,
+    #[serde(default)]
+    tags: Vec<ExperimentTag>,
+}
+
+impl Experiment {
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn new(client: &MlflowClient, name: &str) -> Result<Self, Error> {
+        let response: CreateExperimentResponse = client.checked_post_request_async(
+            "api/2.0/mlflow/experiments/create",
+            &CreateExperimentRequest {
+                name: name.to_owned(),
+                tags: vec![],
+            },
+        )
+        .await
+        .inspect_err(|_| {
+            error!("an experiment with the name {} might exist already or still exists in a deleted state.", name);
+        })?;
+
+        Self::search_with_id(client, &response.experiment_id).await
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn new(_: &MlflowClient, _: &str) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn search_with_id(client: &MlflowClient, id: &str) -> Result<Self, Error> {
+        let response: GetExperimentResponse<Self> = client
+            .checked_get_request_async(
+                "api/2.0/mlflow/experiments/get",
+                &GetExperimentRequest {
+                    experiment_id: id.to_owned(),
+                },
+            )
+            .await?;
+
+        let mut experiment = response.experiment;
+        experiment.client = client.clone();
+
+        Ok(experiment)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn search_with_id(_: &MlflowClient, _: &str) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn search_with_name(client: &MlflowClient, name: &str) -> Result<Self, Error> {
+        let response: GetExperimentResponse<Self> = client
+            .checked_get_request_async(
+                "api/2.0/mlflow/experiments/get-by-name",
+                &GetExperimentByNameRequest {
+                    experiment_name: name.to_owned(),
+                },
+            )
+            .await?;
+
+        let mut experiment = response.experiment;
+        experiment.client = client.clone();
+
+        Ok(experiment)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn search_with_name(_: &MlflowClient, _: &str) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    async fn create_run_unchecked(
+        &self,
+        run_name: Option<&str>,
+        mut tags: Vec<RunTag>,
+    ) -> Result<Run, Error> {
+        let tags = run_git(move || {
+            add_source_tags(&mut tags)?;
+            Ok(tags)
+        })
+        .await?;
+
+        let start_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_millis();
+
+        let response: CreateRunResponse<Run> = self
+            .client
+            .checked_post_request_async(
+                "api/2.0/mlflow/runs/create",
+                &CreateRunRequest {
+                    experiment_id: self.experiment_id.clone(),
+                    run_name: run_name.map(|x| x.to_owned()),
+                    start_time,
+                    tags,
+                },
+            )
+            .await?;
+
+        let mut run = response.run;
+        run.set_client(&self.client);
+
+        Ok(run)
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn create_run(
+        &self,
+        run_name: Option<&str>,
+        tags: Vec<RunTag>,
+    ) -> Result<Run, Error> {
+        run_git(|| {
+            check_repo_is_supported()?;
+
+            if !is_repo_clean()? {
+                Err(Error::DirtyRepo)?
+            }
+
+            Ok(())
+        })
+        .await?;
+
+        self.create_run_unchecked(run_name, tags).await
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn create_run(&self, _: Option<&str>, _: Vec<RunTag>) -> Result<Run, Error> {
+        Ok(Run::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn create_run_with_git_diff(
+        &self,
+        run_name: Option<&str>,
+        tags: Vec<RunTag>,
+    ) -> Result<Run, Error> {
+        run_git(check_repo_is_supported).await?;
+
+        let run = self.create_run_unchecked(run_name, tags).await?;
+        let diff = run_git(|| {
+            if is_repo_clean()? {
+                Ok(None)
+            } else {
+                create_diff().map(Some)
+            }
+        })
+        .await?;
+
+        if let Some(diff) = diff {
+            run.log_artifact_bytes(diff, "uncommitted.patch").await?;
+        }
+
+        Ok(run)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn create_run_with_git_diff(
+        &self,
+        _: Option<&str>,
+        _: Vec<RunTag>,
+    ) -> Result<Run, Error> {
+        Ok(Run::default())
+    }
+
+    pub fn get_experiment_id(&self) -> &str {
+        &self.experiment_id
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_artifact_location(&self) -> &str {
+        &self.artifact_location
+    }
+
+    pub fn get_lifecycle_stage(&self) -> &str {
+        &self.lifecycle_stage
+    }
+
+    pub fn get_last_update_time(&self) -> u64 {
+        self.last_update_time
+    }
+
+    pub fn get_creating_time(&self) -> u64 {
+        self.creation_time
+    }
+
+    pub fn get_tags(&self) -> &Vec<ExperimentTag> {
+        &self.tags
+    }
+}
+
+/// Runs the git commands on a thread of the blocking pool, so they don't block the executor.
+#[cfg(not(disable_experiment_tracking))]
+async fn run_git<T: Send + 'static>(
+    git_commands: impl FnOnce() -> Result<T, Error> + Send + 'static,
+) -> Result<T, Error> {
+    tokio::task::spawn_blocking(git_commands)
+        .await
+        .map_err(|e| Error::Other(Box::new(e)))?
+}
@@ -0,0 +1,4 @@
+//! Non-blocking versions of [`crate::experiment::Experiment`] and [`crate::run::Run`] for use inside async runtimes.
+
+pub mod experiment;
+pub mod run;
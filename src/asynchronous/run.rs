@@ -0,0 +1,493 @@
+use std::{collections::HashMap, path::Path, time::SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    artifacts::{proxied_artifact_url, proxied_listing_paths},
+    client::MlflowClient,
+    error::Error,
+    run::{
+        split_log_batch, ArtifactInfo, Metric, Run as BlockingRun, RunData, RunInfo, RunParameters,
+        RunTag, Status,
+    },
+    schemas::{
+        GetRunRequest, GetRunResponse, ListArtifactsRequest, ListArtifactsResponse,
+        ListProxiedArtifactsRequest, LogBatchRequest, LogBatchResponse, LogMetricRequest,
+        LogMetricResponse, LogParameterRequest, LogParameterResponse, UpdateRunRequest,
+        UpdateRunResponse,
+    },
+};
+
+#[derive(Deserialize, Default)]
+pub struct Run {
+    #[serde(skip)]
+    client: MlflowClient,
+    info: RunInfo,
+    data: RunData,
+}
+
+impl Run {
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn get_run(client: &MlflowClient, run_id: &str) -> Result<Self, Error> {
+        let mut run = client
+            .checked_get_request_async::<GetRunRequest, GetRunResponse<Self>>(
+                "api/2.0/mlflow/runs/get",
+                &GetRunRequest {
+                    run_id: run_id.to_owned(),
+                },
+            )
+            .await?
+            .run;
+
+        run.set_client(client);
+
+        Ok(run)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn get_run(_: &MlflowClient, _: &str) -> Result<Self, Error> {
+        Ok(Run::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn end_run(&mut self, status: Status) -> Result<(), Error> {
+        let end_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_millis();
+
+        let new_run_info = self
+            .client
+            .checked_post_request_async::<UpdateRunRequest, UpdateRunResponse>(
+                "api/2.0/mlflow/runs/update",
+                &UpdateRunRequest {
+                    run_id: self.info.run_id.clone(),
+                    status,
+                    end_time,
+                },
+            )
+            .await?
+            .run_info;
+
+        self.info = new_run_info;
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn end_run(&mut self, _: Status) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_metric(&self, key: &str, value: f32, step: Option<u64>) -> Result<(), Error> {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_millis();
+
+        self.client
+            .checked_post_request_async::<LogMetricRequest, LogMetricResponse>(
+                "api/2.0/mlflow/runs/log-metric",
+                &LogMetricRequest {
+                    run_id: self.info.run_id.clone(),
+                    key: key.to_owned(),
+                    value,
+                    timestamp,
+                    step,
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_metric(&self, _: &str, _: f32, _: Option<u64>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_parameter(&self, key: &str, value: &str) -> Result<(), Error> {
+        self.client
+            .checked_post_request_async::<LogParameterRequest, LogParameterResponse>(
+                "api/2.0/mlflow/runs/log-parameter",
+                &LogParameterRequest {
+                    run_id: self.info.run_id.clone(),
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_parameter(&self, _: &str, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Logs metrics, parameters and tags with as few requests as possible.
+    /// The data is split into multiple requests if it exceeds the limits of the server.
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_batch(
+        &self,
+        metrics: &[Metric],
+        params: &[RunParameters],
+        tags: &[RunTag],
+    ) -> Result<(), Error> {
+        for request in split_log_batch(&self.info.run_id, metrics, params, tags) {
+            self.client
+                .checked_post_request_async::<LogBatchRequest, LogBatchResponse>(
+                    "api/2.0/mlflow/runs/log-batch",
+                    &request,
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_batch(
+        &self,
+        _: &[Metric],
+        _: &[RunParameters],
+        _: &[RunTag],
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_metrics<K: AsRef<str>>(
+        &self,
+        metrics: &HashMap<K, f32>,
+        step: Option<u64>,
+    ) -> Result<(), Error> {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_millis() as u64;
+
+        let metrics: Vec<Metric> = metrics
+            .iter()
+            .map(|(key, value)| Metric {
+                key: key.as_ref().to_owned(),
+                value: *value,
+                timestamp,
+                step,
+            })
+            .collect();
+
+        self.log_batch(&metrics, &[], &[]).await
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_metrics<K: AsRef<str>>(
+        &self,
+        _: &HashMap<K, f32>,
+        _: Option<u64>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_parameters<K: AsRef<str>, V: AsRef<str>>(
+        &self,
+        params: &HashMap<K, V>,
+    ) -> Result<(), Error> {
+        let params: Vec<RunParameters> = params
+            .iter()
+            .map(|(key, value)| RunParameters {
+                key: key.as_ref().to_owned(),
+                value: value.as_ref().to_owned(),
+            })
+            .collect();
+
+        self.log_batch(&[], &params, &[]).await
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_parameters<K: AsRef<str>, V: AsRef<str>>(
+        &self,
+        _: &HashMap<K, V>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_parameter_struct_as_json<T: Serialize>(
+        &self,
+        parameters: T,
+    ) -> Result<(), Error> {
+        let parsed = serde_json::to_value(parameters)?;
+        let mut params = Vec::new();
+        BlockingRun::collect_serde_value_as_parameters("", parsed, &mut params)?;
+
+        self.log_batch(&[], &params, &[]).await
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_parameter_struct_as_json<T: Serialize>(&self, _: T) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn list_artifacts(&self, prefix: &str) -> Result<Vec<ArtifactInfo>, Error> {
+        if let Some(proxied_path) = self.proxied_path(prefix)? {
+            let response = self
+                .client
+                .checked_get_request_async::<ListProxiedArtifactsRequest, ListArtifactsResponse>(
+                    "api/2.0/mlflow-artifacts/artifacts",
+                    &ListProxiedArtifactsRequest { path: proxied_path },
+                )
+                .await?;
+
+            return Ok(proxied_listing_paths(prefix, response.files));
+        }
+
+        let response = self
+            .client
+            .checked_get_request_async::<ListArtifactsRequest, ListArtifactsResponse>(
+                "api/2.0/mlflow/artifacts/list",
+                &ListArtifactsRequest {
+                    run_id: self.info.run_id.clone(),
+                    path: prefix.to_string(),
+                },
+            )
+            .await?;
+
+        Ok(response.files)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn list_artifacts(&self, _: &str) -> Result<Vec<ArtifactInfo>, Error> {
+        Ok(Vec::new())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_artifact_file(
+        &self,
+        path_on_disk: &Path,
+        path_destination: &str,
+    ) -> Result<(), Error> {
+        let data = tokio::fs::read(path_on_disk).await?;
+
+        self.log_artifact_bytes(data, path_destination).await
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_artifact_file(&self, _: &Path, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_artifact_bytes(
+        &self,
+        data: Vec<u8>,
+        path_destination: &str,
+    ) -> Result<(), Error> {
+        let client = self.client.async_client()?;
+        let request = match self.proxied_path(path_destination)? {
+            Some(proxied_path) => client.put(proxied_artifact_url(&self.client, &proxied_path)?),
+            None => client
+                .post(self.client.url("ajax-api/2.0/mlflow/upload-artifact"))
+                .query(&[
+                    ("run_uuid", self.info.run_id.as_str()),
+                    ("path", path_destination),
+                ]),
+        };
+
+        self.client.send_async(request.body(data)).await?;
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_artifact_bytes(&self, _: Vec<u8>, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_artifact_struct_as_json<T: Serialize>(
+        &self,
+        data_struct: T,
+        path_destination: &str,
+    ) -> Result<(), Error> {
+        let data = serde_json::to_string(&data_struct)?.into_bytes();
+
+        self.log_artifact_bytes(data, path_destination).await
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_artifact_struct_as_json<T: Serialize>(
+        &self,
+        _: T,
+        _: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn log_artifact_struct_as_binary<T: Serialize>(
+        &self,
+        data_struct: T,
+        path_destination: &str,
+    ) -> Result<(), Error> {
+        let data = bincode::serialize(&data_struct)?;
+
+        self.log_artifact_bytes(data, path_destination).await
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn log_artifact_struct_as_binary<T: Serialize>(
+        &self,
+        _: T,
+        _: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn get_artifact_as_bytes(&self, path: &str) -> Result<Vec<u8>, Error> {
+        let client = self.client.async_client()?;
+        let request = match self.proxied_path(path)? {
+            Some(proxied_path) => client.get(proxied_artifact_url(&self.client, &proxied_path)?),
+            None => client
+                .get(self.client.url("get-artifact"))
+                .query(&[("path", path), ("run_uuid", self.info.run_id.as_str())]),
+        };
+        let response = self.client.send_async(request).await?;
+
+        Ok(response.bytes().await?.into())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn get_artifact_as_bytes(&self, _: &str) -> Result<Vec<u8>, Error> {
+        Err(Error::TrackingDisabled)
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn get_artifact_as_string(&self, path: &str) -> Result<String, Error> {
+        Ok(String::from_utf8(self.get_artifact_as_bytes(path).await?)?)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn get_artifact_as_string(&self, _: &str) -> Result<String, Error> {
+        Err(Error::TrackingDisabled)
+    }
+
+    /// Deletes the file or directory. This is only supported by the artifact proxy of the tracking server.
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn delete_artifact(&self, path: &str) -> Result<(), Error> {
+        let proxied_path = self.proxied_path(path)?.ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "the artifacts at {} cannot be deleted without the artifact proxy",
+                self.info.artifact_uri
+            ))
+        })?;
+
+        let request = self
+            .client
+            .async_client()?
+            .delete(proxied_artifact_url(&self.client, &proxied_path)?);
+
+        self.client.send_async(request).await?;
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn delete_artifact(&self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    fn proxied_path(&self, path: &str) -> Result<Option<String>, Error> {
+        self.client
+            .get_artifact_backend()
+            .proxied_path(&self.info.artifact_uri, path)
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn get_artifact_binary_as_struct<T>(&self, path: &str) -> Result<T, Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let bytes = self.get_artifact_as_bytes(path).await?;
+
+        Ok(bincode::deserialize(&bytes)?)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn get_artifact_binary_as_struct<T>(&self, _: &str) -> Result<T, Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Err(Error::TrackingDisabled)
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub async fn get_artifact_json_as_struct<T>(&self, path: &str) -> Result<T, Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let text = self.get_artifact_as_string(path).await?;
+
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub async fn get_artifact_json_as_struct<T>(&self, _: &str) -> Result<T, Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Err(Error::TrackingDisabled)
+    }
+
+    pub fn get_client(&self) -> &MlflowClient {
+        &self.client
+    }
+
+    pub fn set_client(&mut self, client: &MlflowClient) {
+        self.client = client.clone()
+    }
+
+    pub fn get_run_uuid(&self) -> &str {
+        &self.info.run_uuid
+    }
+
+    pub fn get_experiment_id(&self) -> &str {
+        &self.info.experiment_id
+    }
+
+    pub fn get_run_name(&self) -> &str {
+        &self.info.run_name
+    }
+
+    pub fn get_user_id(&self) -> &str {
+        &self.info.user_id
+    }
+
+    pub fn get_status(&self) -> &str {
+        &self.info.status
+    }
+
+    pub fn get_start_time(&self) -> u64 {
+        self.info.start_time
+    }
+
+    pub fn get_artifact_uri(&self) -> &str {
+        &self.info.artifact_uri
+    }
+
+    pub fn get_lifecycle_stage(&self) -> &str {
+        &self.info.lifecycle_stage
+    }
+
+    pub fn get_tags(&self) -> &Vec<RunTag> {
+        &self.data.tags
+    }
+
+    pub fn get_parameters(&self) -> &Vec<RunParameters> {
+        &self.data.params
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::{BTreeMap, HashMap},
     error::Error,
     path::Path,
     sync::{
@@ -11,36 +12,366 @@ use std::{
 
 use log::{error, info, Log};
 use mlflow_rs::{
-    experiment::Experiment,
+    artifacts::{ArtifactBackend, MultipartUploadConfig, UploadDirOptions},
+    async_logging::{AsyncLoggingConfig, FullQueuePolicy},
+    client::{MlflowClient, RetryPolicy},
+    dataset::Dataset,
+    experiment::{Experiment, ExperimentTag, SearchExperimentsOptions, ViewType},
+    filter::{Comparator, RunFilter},
     logger::ExperimentLogger,
-    run::{Run, RunTag, Status},
+    offline,
+    registry::{model_version::Stage, registered_model::RegisteredModel},
+    run::{Run, RunTag, SearchRunsOptions, Status},
 };
 use serde::{Deserialize, Serialize};
 
 #[allow(dead_code)]
 fn create_experiment() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::new(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::new(&client, "test")?;
 
     println!("Created experiment {}", experiment.get_name());
 
     Ok(())
 }
 
+#[allow(dead_code)]
+fn create_experiment_with_tags() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+
+    let mut experiment = Experiment::new_with_tags(
+        &client,
+        "tagged",
+        vec![ExperimentTag {
+            key: "team".to_owned(),
+            value: "research".to_owned(),
+        }],
+    )?;
+
+    experiment.set_tag("project", "mlflow-rs")?;
+    experiment.delete_tag("team")?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn delete_experiment() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let mut experiment = Experiment::new(&client, "throwaway")?;
+
+    experiment.rename("throwaway-renamed")?;
+    experiment.delete()?;
+    println!("{}", experiment.get_lifecycle_stage());
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn create_configured_client() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::builder("http://localhost:5000")
+        .header("X-Team", "research")
+        .timeout(Duration::from_secs(60))
+        .retry_policy(RetryPolicy {
+            max_retries: 3,
+            backoff: Duration::from_secs(1),
+        })
+        .build()?;
+
+    let experiment = Experiment::search_with_name(&client, "test")?;
+
+    println!("Got experiment {}", experiment.get_name());
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn create_authenticated_client() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::builder("http://localhost:5000")
+        .bearer_token("token")
+        .build()?;
+
+    let experiment = Experiment::search_with_name(&client, "test")?;
+
+    println!("Got experiment {}", experiment.get_name());
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn get_or_create_experiment() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::get_or_create(&client, "test", true)?;
+
+    println!("Got experiment {}", experiment.get_name());
+
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn search_experiment_with_id() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_id(api_root, "1")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_id(&client, "1")?;
 
     println!("Got experiment {}", experiment.get_name());
 
     Ok(())
 }
 
+#[allow(dead_code)]
+fn search_experiments() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+
+    let experiments = Experiment::search(
+        &client,
+        SearchExperimentsOptions {
+            filter: Some("name LIKE 'test%'".to_owned()),
+            order_by: vec!["name ASC".to_owned()],
+            view_type: ViewType::All,
+            ..Default::default()
+        },
+    );
+
+    for experiment in experiments {
+        println!("Found experiment {}", experiment?.get_name());
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn search_runs() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
+
+    let filter = RunFilter::new()
+        .metric("loss", Comparator::LessThan, 0.1)
+        .param("lr", Comparator::Equal, "0.01")
+        .attribute("status", Comparator::Equal, "FINISHED")
+        .build()?;
+
+    let runs = experiment.search_runs(SearchRunsOptions {
+        filter: Some(filter),
+        order_by: vec!["metrics.loss ASC".to_owned()],
+        max_results: Some(10),
+        ..Default::default()
+    });
+
+    for run in runs {
+        println!("Found run {}", run?.get_run_name());
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn delete_failed_runs() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
+
+    let options = SearchRunsOptions {
+        filter: Some(
+            RunFilter::new()
+                .attribute("status", Comparator::Equal, "FAILED")
+                .build()?,
+        ),
+        ..Default::default()
+    };
+
+    for run in experiment.delete_runs(options.clone(), true)? {
+        println!("would delete {}", run.get_run_name());
+    }
+
+    experiment.delete_runs(options, false)?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn register_model() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+
+    let mut model = RegisteredModel::create(&client, "classifier", Some("first try"), vec![])?;
+    model.update_description("image classifier")?;
+    model.set_tag("team", "research")?;
+
+    for model in RegisteredModel::search(&client, Default::default()) {
+        println!("Found model {}", model?.get_name());
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn log_dataset() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let mut run = Run::get_run(&client, "run_id")?;
+
+    let dataset = Dataset::from_local_path(Path::new("data/train.csv"))?;
+    run.log_input(&dataset, Some("training"))?;
+
+    for input in run.get_dataset_inputs() {
+        println!("{} ({})", input.dataset.name, input.dataset.digest);
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn log_large_artifact() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "run_id")?;
+
+    run.log_artifact_file_multipart(
+        Path::new("checkpoint.pt"),
+        "checkpoints/checkpoint.pt",
+        &MultipartUploadConfig {
+            chunk_size: 50 * 1024 * 1024,
+            ..Default::default()
+        },
+    )?;
+
+    let file = std::fs::File::open("train.log")?;
+    run.log_artifact_reader(file, "train.log")?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn log_artifacts_dir() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "run_id")?;
+
+    let summary = run.log_artifacts_dir(
+        Path::new("output"),
+        "output",
+        &UploadDirOptions {
+            exclude: vec!["*.tmp".to_owned()],
+            max_parallel_uploads: 4,
+            ..Default::default()
+        },
+    )?;
+
+    for (path, e) in &summary.failed {
+        error!("could not upload {path}: {e}");
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn download_artifacts() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "run_id")?;
+
+    for path in run.download_artifacts("checkpoints", Path::new("downloads"))? {
+        info!("downloaded {}", path.display());
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn download_large_artifact() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "run_id")?;
+
+    // continues an interrupted download of the same file
+    run.download_artifact_to_file("model.bin", Path::new("model.bin"), true, |progress| {
+        if let Some(total_bytes) = progress.total_bytes {
+            info!("downloaded {} of {total_bytes} bytes", progress.bytes_done);
+        }
+    })?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn delete_artifacts() -> Result<(), Box<dyn Error>> {
+    // deleting artifacts requires the artifact proxy
+    let client = MlflowClient::builder("http://localhost:5000")
+        .artifact_backend(ArtifactBackend::Proxy)
+        .build()?;
+    let run = Run::get_run(&client, "run_id")?;
+
+    run.delete_artifact("checkpoints")?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn log_artifacts_to_shared_mount() -> Result<(), Box<dyn Error>> {
+    // the artifact URIs of the runs are file: paths on a mount that is shared with the tracking server
+    let client = MlflowClient::builder("http://localhost:5000")
+        .artifact_backend(ArtifactBackend::Local)
+        .build()?;
+    let run = Run::get_run(&client, "run_id")?;
+
+    run.log_artifact_file(Path::new("model.bin"), "model.bin")?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn log_offline_and_sync() -> Result<(), Box<dyn Error>> {
+    let journal_dir = Path::new("mlruns-offline");
+    let mut run = offline::create_run(journal_dir, "test", Some("offline run"), vec![])?;
+
+    run.log_parameter("learning_rate", "0.001")?;
+    run.log_metric("metric", 42.0, Some(0))?;
+    run.log_artifact_bytes(b"test data".to_vec(), "test.txt")?;
+    run.end_run(Status::Finished)?;
+
+    // later, when the tracking server is reachable
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let summary = offline::sync(&client, journal_dir)?;
+
+    for synced_run in &summary.synced {
+        println!(
+            "{} -> {}",
+            synced_run.local_run_id, synced_run.server_run_id
+        );
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn log_model() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let mut run = Run::get_run(&client, "run_id")?;
+
+    let mut flavors = BTreeMap::new();
+    flavors.insert(
+        "rust".to_owned(),
+        serde_json::json!({"format": "bincode", "data": "model.bin"}),
+    );
+
+    run.log_model("model", &[Path::new("model.bin")], flavors, None)?;
+
+    let descriptor = run.load_model("model", Path::new("downloaded_model"))?;
+    println!("model was created at {}", descriptor.utc_time_created);
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn register_model_version() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "run_id")?;
+
+    let mut version = run.register_model("model", "classifier")?;
+    version.set_alias("champion")?;
+    version.transition_stage(Stage::Staging, false)?;
+
+    println!("registered version {}", version.get_version());
+
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn create_run_without_git_diff() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     experiment.create_run(Some("new run"), vec![])?;
 
@@ -49,8 +380,8 @@ fn create_run_without_git_diff() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn create_run_with_git_diff() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -59,8 +390,8 @@ fn create_run_with_git_diff() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn create_run_with_tags() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     experiment.create_run_with_git_diff(
         Some("new run"),
@@ -73,10 +404,25 @@ fn create_run_with_tags() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[allow(dead_code)]
+fn update_run_tags() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let mut run = Run::get_run(&client, "run_id")?;
+
+    run.set_tag("stage", "evaluation")?;
+    run.delete_tag("draft")?;
+
+    for tag in run.get_tags() {
+        println!("{}: {}", tag.key, tag.value);
+    }
+
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn end_run() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let mut run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -87,8 +433,8 @@ fn end_run() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn log_metrics() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -99,10 +445,26 @@ fn log_metrics() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[allow(dead_code)]
+fn get_metric_history() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "run_id")?;
+
+    if let Some(loss) = run.get_metric("loss") {
+        println!("latest loss: {}", loss.value);
+    }
+
+    for metric in run.get_metric_history("loss")? {
+        println!("step {:?}: {}", metric.step, metric.value);
+    }
+
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn log_params() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -111,6 +473,47 @@ fn log_params() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[allow(dead_code)]
+fn log_batch() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
+
+    let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
+
+    let mut metrics = HashMap::new();
+    metrics.insert("mse", 1.4);
+    metrics.insert("mae", 0.8);
+    run.log_metrics(&metrics, Some(0))?;
+
+    let mut params = HashMap::new();
+    params.insert("param1", "value1");
+    params.insert("param2", "value2");
+    run.log_parameters(&params)?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn async_logging() -> Result<(), Box<dyn Error>> {
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
+
+    let mut run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
+
+    run.enable_async_logging(AsyncLoggingConfig {
+        capacity: 1000,
+        full_queue_policy: FullQueuePolicy::Block,
+    });
+
+    for step in 0..100 {
+        run.log_metric("mse", 1.0 / (step + 1) as f32, Some(step))?;
+    }
+
+    run.end_run(Status::Finished)?; // waits until all metrics were sent
+
+    Ok(())
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct ParamStruct {
     a: String,
@@ -127,8 +530,8 @@ struct InnerStruct {
 
 #[allow(dead_code)]
 fn log_struct_params() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -147,8 +550,8 @@ fn log_struct_params() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn log_file() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -159,8 +562,8 @@ fn log_file() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn log_bytes() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -171,8 +574,8 @@ fn log_bytes() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn log_struct_as_json() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
     let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
     let data = ParamStruct {
@@ -192,8 +595,8 @@ fn log_struct_as_json() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn log_struct_as_binary() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
     let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
     let data = ParamStruct {
@@ -245,8 +648,8 @@ fn log_logger() -> Result<(), Box<dyn Error>> {
 
     log::set_max_level(log::LevelFilter::Trace);
 
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -273,8 +676,8 @@ fn experiment_function(run: &Run, _: Arc<AtomicBool>, _: ()) -> Result<(), Box<d
 
 #[allow(dead_code)]
 fn run_experiment() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let mut run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -285,8 +688,8 @@ fn run_experiment() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn run_experiment_with_logger() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let logger = TestLogger {};
     log::set_max_level(log::LevelFilter::Info);
@@ -300,8 +703,8 @@ fn run_experiment_with_logger() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn ctrl_c_handler() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let mut run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -318,8 +721,8 @@ fn ctrl_c_handler() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn ctrl_c_handler_ignore_signal() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let experiment = Experiment::search_with_name(api_root, "test")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let experiment = Experiment::search_with_name(&client, "test")?;
 
     let mut run = experiment.create_run_with_git_diff(Some("new run"), vec![])?;
 
@@ -336,8 +739,8 @@ fn ctrl_c_handler_ignore_signal() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn get_run() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let run = Run::get_run(api_root, "e089ac98e5bf46bd9c952b50a6c27889")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "e089ac98e5bf46bd9c952b50a6c27889")?;
 
     println!("{}", run.get_run_name());
 
@@ -346,8 +749,8 @@ fn get_run() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn get_artifact_as_bytes() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let run = Run::get_run(api_root, "f11fa50bbfa0412cbabece559d9a499b")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "f11fa50bbfa0412cbabece559d9a499b")?;
 
     let log = String::from_utf8(run.get_artifact_as_bytes("log.log")?)?;
 
@@ -358,8 +761,8 @@ fn get_artifact_as_bytes() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn get_artifact_as_string() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let run = Run::get_run(api_root, "f11fa50bbfa0412cbabece559d9a499b")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "f11fa50bbfa0412cbabece559d9a499b")?;
 
     let log = run.get_artifact_as_string("log.log")?;
 
@@ -370,8 +773,8 @@ fn get_artifact_as_string() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn get_artifact_json_as_struct() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let run = Run::get_run(api_root, "7c758834d40e4226926084560f21aadc")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "7c758834d40e4226926084560f21aadc")?;
 
     let data: ParamStruct = run.get_artifact_json_as_struct("test.json")?;
 
@@ -382,8 +785,8 @@ fn get_artifact_json_as_struct() -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn get_artifact_binary_as_struct() -> Result<(), Box<dyn Error>> {
-    let api_root = "http://localhost:5000";
-    let run = Run::get_run(api_root, "7c758834d40e4226926084560f21aadc")?;
+    let client = MlflowClient::new("http://localhost:5000")?;
+    let run = Run::get_run(&client, "7c758834d40e4226926084560f21aadc")?;
 
     let data: ParamStruct = run.get_artifact_binary_as_struct("test.bin")?;
 
@@ -0,0 +1,56 @@
+use std::{env, error::Error, path::PathBuf, process::exit};
+
+use mlflow_rs::{client::MlflowClient, offline};
+
+const USAGE: &str = "usage: mlflow-sync <journal_dir> [tracking_uri]
+
+Sends the offline runs in <journal_dir> to the tracking server.
+The tracking URI defaults to MLFLOW_TRACKING_URI, credentials are read from
+MLFLOW_TRACKING_USERNAME and MLFLOW_TRACKING_PASSWORD or MLFLOW_TRACKING_TOKEN.";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let (journal_dir, tracking_uri) = match args.as_slice() {
+        [journal_dir] => (journal_dir, env::var("MLFLOW_TRACKING_URI").ok()),
+        [journal_dir, tracking_uri] => (journal_dir, Some(tracking_uri.clone())),
+        _ => {
+            eprintln!("{USAGE}");
+            exit(2);
+        }
+    };
+
+    let Some(tracking_uri) = tracking_uri else {
+        eprintln!("no tracking URI given and MLFLOW_TRACKING_URI is not set");
+        exit(2);
+    };
+
+    let client = MlflowClient::new(&tracking_uri)?;
+    let summary = offline::sync(&client, &PathBuf::from(journal_dir))?;
+
+    for run in &summary.synced {
+        println!(
+            "{} -> {} ({} new entries)",
+            run.local_run_id, run.server_run_id, run.replayed_entries
+        );
+
+        for (line, e) in &run.failed_entries {
+            eprintln!("{} entry {line} was rejected: {e}", run.local_run_id);
+        }
+    }
+
+    for (local_run_id, e) in &summary.failed {
+        eprintln!("{local_run_id} failed: {e}");
+    }
+
+    if !summary.is_success() {
+        exit(1);
+    }
+
+    Ok(())
+}
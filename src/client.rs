@@ -0,0 +1,515 @@
+use std::{
+    env,
+    panic::RefUnwindSafe,
+    sync::{Arc, OnceLock},
+    thread,
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION},
+    Method, StatusCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{artifacts::ArtifactBackend, error::Error, utils::check_for_error_response};
+
+/// Decides how often and how long to wait before a failed request is sent again.
+/// Only transport errors and responses with the status 429 or 5xx are retried.
+/// POST and PATCH requests are only retried if the server cannot have processed them,
+/// i.e. after connection errors and responses with the status 429 or 503.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    /// The n-th retry waits n times this duration.
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            backoff: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Credentials that are sent with every request to the tracking server.
+#[derive(Clone)]
+pub enum Authentication {
+    Basic { username: String, password: String },
+    Bearer { token: String },
+}
+
+impl Authentication {
+    /// Reads the credentials from the same environment variables as the official MLflow client.
+    /// Basic auth takes precedence over the token if both are set.
+    pub fn from_env() -> Option<Self> {
+        let username = env::var("MLFLOW_TRACKING_USERNAME").ok();
+        let password = env::var("MLFLOW_TRACKING_PASSWORD").ok();
+
+        if let (Some(username), Some(password)) = (username, password) {
+            return Some(Self::Basic { username, password });
+        }
+
+        env::var("MLFLOW_TRACKING_TOKEN")
+            .ok()
+            .map(|token| Self::Bearer { token })
+    }
+
+    fn header_value(&self) -> Result<HeaderValue, Error> {
+        let value = match self {
+            Self::Basic { username, password } => {
+                format!(
+                    "Basic {}",
+                    STANDARD.encode(format!("{username}:{password}"))
+                )
+            }
+            Self::Bearer { token } => format!("Bearer {token}"),
+        };
+
+        let mut value = HeaderValue::from_str(&value)
+            .map_err(|e| Error::InvalidInput(format!("invalid credentials: {e}")))?;
+        value.set_sensitive(true);
+
+        Ok(value)
+    }
+}
+
+pub struct MlflowClientBuilder {
+    api_root: String,
+    headers: Vec<(String, String)>,
+    authentication: Option<Authentication>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    artifact_backend: ArtifactBackend,
+}
+
+impl MlflowClientBuilder {
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Overrides the credentials from the environment variables.
+    pub fn authentication(mut self, authentication: Authentication) -> Self {
+        self.authentication = Some(authentication);
+        self
+    }
+
+    pub fn basic_auth(self, username: &str, password: &str) -> Self {
+        self.authentication(Authentication::Basic {
+            username: username.to_owned(),
+            password: password.to_owned(),
+        })
+    }
+
+    pub fn bearer_token(self, token: &str) -> Self {
+        self.authentication(Authentication::Bearer {
+            token: token.to_owned(),
+        })
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn artifact_backend(mut self, artifact_backend: ArtifactBackend) -> Self {
+        self.artifact_backend = artifact_backend;
+        self
+    }
+
+    pub fn build(self) -> Result<MlflowClient, Error> {
+        let mut default_headers = HeaderMap::new();
+
+        for (name, value) in &self.headers {
+            default_headers.insert(
+                HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|e| Error::InvalidInput(format!("invalid header name {name}: {e}")))?,
+                HeaderValue::from_str(value).map_err(|e| {
+                    Error::InvalidInput(format!("invalid value for header {name}: {e}"))
+                })?,
+            );
+        }
+
+        // the header is a default header, so it is also sent with the artifact up- and downloads
+        if let Some(authentication) = self.authentication.or_else(Authentication::from_env) {
+            default_headers.insert(AUTHORIZATION, authentication.header_value()?);
+        }
+
+        Ok(MlflowClient {
+            inner: Arc::new(ClientInner {
+                api_root: self.api_root.trim_end_matches('/').to_owned(),
+                default_headers,
+                timeout: self.timeout,
+                connect_timeout: self.connect_timeout,
+                retry_policy: self.retry_policy,
+                artifact_backend: self.artifact_backend,
+                blocking_client: OnceLock::new(),
+                external_blocking_client: OnceLock::new(),
+                #[cfg(feature = "async")]
+                async_client: OnceLock::new(),
+            }),
+        })
+    }
+}
+
+/// Connection to an MLflow tracking server which is shared by all experiments and runs created from it.
+/// Cloning is cheap and reuses the underlying connection pool.
+#[derive(Clone, Default)]
+pub struct MlflowClient {
+    inner: Arc<ClientInner>,
+}
+
+// the HTTP clients are connection pools that stay usable if a caller panics during a request
+impl RefUnwindSafe for MlflowClient {}
+
+#[derive(Default)]
+struct ClientInner {
+    api_root: String,
+    default_headers: HeaderMap,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    artifact_backend: ArtifactBackend,
+    // created on first use, because a blocking client must not be dropped inside an async runtime
+    blocking_client: OnceLock<reqwest::blocking::Client>,
+    // without the default headers, so credentials of the tracking server are not sent to other hosts
+    external_blocking_client: OnceLock<reqwest::blocking::Client>,
+    #[cfg(feature = "async")]
+    async_client: OnceLock<reqwest::Client>,
+}
+
+impl MlflowClient {
+    pub fn new(api_root: &str) -> Result<Self, Error> {
+        Self::builder(api_root).build()
+    }
+
+    pub fn builder(api_root: &str) -> MlflowClientBuilder {
+        MlflowClientBuilder {
+            api_root: api_root.to_owned(),
+            headers: Vec::new(),
+            authentication: None,
+            timeout: None,
+            connect_timeout: None,
+            retry_policy: RetryPolicy::default(),
+            artifact_backend: ArtifactBackend::default(),
+        }
+    }
+
+    pub fn get_api_root(&self) -> &str {
+        &self.inner.api_root
+    }
+
+    pub fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.inner.retry_policy
+    }
+
+    pub fn get_artifact_backend(&self) -> ArtifactBackend {
+        self.inner.artifact_backend
+    }
+
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.inner.api_root, path)
+    }
+
+    pub(crate) fn blocking_client(&self) -> Result<&reqwest::blocking::Client, Error> {
+        self.init_blocking_client(&self.inner.blocking_client, true)
+    }
+
+    /// Client for requests to other hosts than the tracking server, e.g. presigned URLs of a storage service.
+    pub(crate) fn external_blocking_client(&self) -> Result<&reqwest::blocking::Client, Error> {
+        self.init_blocking_client(&self.inner.external_blocking_client, false)
+    }
+
+    fn init_blocking_client<'a>(
+        &self,
+        cell: &'a OnceLock<reqwest::blocking::Client>,
+        with_default_headers: bool,
+    ) -> Result<&'a reqwest::blocking::Client, Error> {
+        if let Some(client) = cell.get() {
+            return Ok(client);
+        }
+
+        let mut builder = reqwest::blocking::Client::builder();
+
+        if with_default_headers {
+            builder = builder.default_headers(self.inner.default_headers.clone());
+        }
+
+        if let Some(timeout) = self.inner.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(connect_timeout) = self.inner.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        let client = builder.build()?;
+
+        Ok(cell.get_or_init(|| client))
+    }
+
+    /// Sends the request and retries it according to the retry policy if the body can be cloned.
+    pub(crate) fn send(
+        &self,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        self.send_with_retry_policy(request, &self.inner.retry_policy)
+    }
+
+    pub(crate) fn send_with_retry_policy(
+        &self,
+        request: reqwest::blocking::RequestBuilder,
+        retry_policy: &RetryPolicy,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        check_status(self.send_unchecked_with_retry_policy(request, retry_policy)?)
+    }
+
+    /// Like [`MlflowClient::send`], but returns unsuccessful responses instead of turning them into errors.
+    pub(crate) fn send_unchecked(
+        &self,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        self.send_unchecked_with_retry_policy(request, &self.inner.retry_policy)
+    }
+
+    fn send_unchecked_with_retry_policy(
+        &self,
+        request: reqwest::blocking::RequestBuilder,
+        retry_policy: &RetryPolicy,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        let idempotent = retry_policy.max_retries > 0
+            && request
+                .try_clone()
+                .and_then(|request| request.build().ok())
+                .is_some_and(|request| is_idempotent(request.method()));
+        let mut retry = 0;
+
+        loop {
+            let retry_request = if retry < retry_policy.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let Some(next_request) = retry_request else {
+                return Ok(request.send()?);
+            };
+
+            match next_request.send() {
+                Ok(response) if is_retryable_status(response.status(), idempotent) => {}
+                Err(e) if is_retryable_error(&e, idempotent) => {}
+                result => return Ok(result?),
+            }
+
+            retry += 1;
+            thread::sleep(retry_policy.backoff * retry);
+        }
+    }
+
+    pub(crate) fn checked_get_request<I: Serialize + ?Sized, O: DeserializeOwned>(
+        &self,
+        path: &str,
+        input: &I,
+    ) -> Result<O, Error> {
+        self.checked_request(Method::GET, path, input)
+    }
+
+    pub(crate) fn checked_post_request<I: Serialize + ?Sized, O: DeserializeOwned>(
+        &self,
+        path: &str,
+        input: &I,
+    ) -> Result<O, Error> {
+        self.checked_request(Method::POST, path, input)
+    }
+
+    pub(crate) fn checked_patch_request<I: Serialize + ?Sized, O: DeserializeOwned>(
+        &self,
+        path: &str,
+        input: &I,
+    ) -> Result<O, Error> {
+        self.checked_request(Method::PATCH, path, input)
+    }
+
+    pub(crate) fn checked_delete_request<I: Serialize + ?Sized, O: DeserializeOwned>(
+        &self,
+        path: &str,
+        input: &I,
+    ) -> Result<O, Error> {
+        self.checked_request(Method::DELETE, path, input)
+    }
+
+    fn checked_request<I: Serialize + ?Sized, O: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        input: &I,
+    ) -> Result<O, Error> {
+        let request = self
+            .blocking_client()?
+            .request(method, self.url(path))
+            .json(input);
+        let response = self.send(request)?;
+        let status = response.status();
+        let response = response.text()?;
+
+        check_for_error_response(status, &response)?;
+
+        Ok(serde_json::from_str(&response)?)
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn async_client(&self) -> Result<&reqwest::Client, Error> {
+        if let Some(client) = self.inner.async_client.get() {
+            return Ok(client);
+        }
+
+        let mut builder =
+            reqwest::Client::builder().default_headers(self.inner.default_headers.clone());
+
+        if let Some(timeout) = self.inner.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(connect_timeout) = self.inner.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        let client = builder.build()?;
+
+        Ok(self.inner.async_client.get_or_init(|| client))
+    }
+
+    /// Sends the request and retries it according to the retry policy if the body can be cloned.
+    #[cfg(feature = "async")]
+    pub(crate) async fn send_async(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, Error> {
+        let retry_policy = &self.inner.retry_policy;
+        let idempotent = retry_policy.max_retries > 0
+            && request
+                .try_clone()
+                .and_then(|request| request.build().ok())
+                .is_some_and(|request| is_idempotent(request.method()));
+        let mut retry = 0;
+
+        loop {
+            let retry_request = if retry < retry_policy.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let Some(next_request) = retry_request else {
+                return check_status_async(request.send().await?).await;
+            };
+
+            match next_request.send().await {
+                Ok(response) if is_retryable_status(response.status(), idempotent) => {}
+                Err(e) if is_retryable_error(&e, idempotent) => {}
+                result => return check_status_async(result?).await,
+            }
+
+            retry += 1;
+            tokio::time::sleep(retry_policy.backoff * retry).await;
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn checked_get_request_async<I: Serialize + ?Sized, O: DeserializeOwned>(
+        &self,
+        path: &str,
+        input: &I,
+    ) -> Result<O, Error> {
+        let request = self.async_client()?.get(self.url(path)).json(input);
+        let response = self.send_async(request).await?;
+        let status = response.status();
+        let response = response.text().await?;
+
+        check_for_error_response(status, &response)?;
+
+        Ok(serde_json::from_str(&response)?)
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn checked_post_request_async<I: Serialize + ?Sized, O: DeserializeOwned>(
+        &self,
+        path: &str,
+        input: &I,
+    ) -> Result<O, Error> {
+        let request = self.async_client()?.post(self.url(path)).json(input);
+        let response = self.send_async(request).await?;
+        let status = response.status();
+        let response = response.text().await?;
+
+        check_for_error_response(status, &response)?;
+
+        Ok(serde_json::from_str(&response)?)
+    }
+}
+
+/// Turns unsuccessful responses into errors, using the error code of the server if the body contains one.
+pub(crate) fn check_status(
+    response: reqwest::blocking::Response,
+) -> Result<reqwest::blocking::Response, Error> {
+    let Err(status_error) = response.error_for_status_ref() else {
+        return Ok(response);
+    };
+
+    let status = response.status();
+    check_for_error_response(status, &response.text()?)?;
+
+    Err(status_error.into())
+}
+
+#[cfg(feature = "async")]
+async fn check_status_async(response: reqwest::Response) -> Result<reqwest::Response, Error> {
+    let Err(status_error) = response.error_for_status_ref() else {
+        return Ok(response);
+    };
+
+    let status = response.status();
+    check_for_error_response(status, &response.text().await?)?;
+
+    Err(status_error.into())
+}
+
+// sending these requests twice has the same effect as sending them once
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+// other errors can occur after the server processed the request, e.g. a 504 of a proxy
+fn is_retryable_status(status: StatusCode, idempotent: bool) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::SERVICE_UNAVAILABLE
+        || (idempotent && status.is_server_error())
+}
+
+fn is_retryable_error(error: &reqwest::Error, idempotent: bool) -> bool {
+    error.is_connect() || (idempotent && (error.is_timeout() || error.is_request()))
+}
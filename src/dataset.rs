@@ -0,0 +1,178 @@
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+
+/// Tag of a dataset input that describes what the dataset was used for.
+pub const CONTEXT_TAG: &str = "mlflow.data.context";
+
+// the python client also shortens digests to 8 characters
+const DIGEST_LENGTH: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Dataset {
+    pub name: String,
+    /// Identifies the content of the dataset, see [`compute_digest`].
+    pub digest: String,
+    /// e.g. `local`, `http` or `s3`
+    pub source_type: String,
+    /// JSON description of the source, e.g. `{"uri": "/data/train.csv"}`
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// JSON summary of the content, see [`compute_profile`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+impl Dataset {
+    /// Describes a local file or directory. The name is the file name and digest and profile are computed from the content.
+    pub fn from_local_path(path: &Path) -> Result<Self, Error> {
+        let path = path.canonicalize()?;
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_owned();
+
+        Ok(Self {
+            name,
+            digest: compute_digest(&path)?,
+            source_type: "local".to_owned(),
+            source: serde_json::json!({ "uri": path.to_string_lossy() }).to_string(),
+            schema: None,
+            profile: Some(serde_json::to_string(&compute_profile(&path)?)?),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct InputTag {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DatasetInput {
+    #[serde(default)]
+    pub tags: Vec<InputTag>,
+    pub dataset: Dataset,
+}
+
+impl DatasetInput {
+    /// e.g. `training` or `evaluation`
+    pub fn get_context(&self) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|tag| tag.key == CONTEXT_TAG)
+            .map(|tag| tag.value.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DatasetProfile {
+    /// Number of lines of all files.
+    pub num_rows: u64,
+    /// Size of all files in bytes.
+    pub file_size: u64,
+    pub num_files: u64,
+}
+
+/// Hashes the content of the file or of all files in the directory together with their relative paths.
+/// Symbolic links to directories inside the directory are skipped.
+pub fn compute_digest(path: &Path) -> Result<String, Error> {
+    let mut hasher = Sha256::new();
+
+    visit_files(path, "", &mut |relative_path, file| {
+        hasher.update(relative_path.as_bytes());
+        hasher.update([0]);
+        read_chunks(file, |chunk| hasher.update(chunk))
+    })?;
+
+    let digest = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+
+    Ok(digest[..DIGEST_LENGTH].to_owned())
+}
+
+/// Counts the lines and bytes of the file or of all files in the directory.
+pub fn compute_profile(path: &Path) -> Result<DatasetProfile, Error> {
+    let mut profile = DatasetProfile::default();
+
+    visit_files(path, "", &mut |_, file| {
+        let mut last_byte = b'\n';
+
+        read_chunks(file, |chunk| {
+            profile.file_size += chunk.len() as u64;
+            profile.num_rows += chunk.iter().filter(|&&byte| byte == b'\n').count() as u64;
+            last_byte = chunk[chunk.len() - 1];
+        })?;
+
+        // the last line does not need to end with a line break
+        if last_byte != b'\n' {
+            profile.num_rows += 1;
+        }
+
+        profile.num_files += 1;
+
+        Ok(())
+    })?;
+
+    Ok(profile)
+}
+
+/// Calls `visit` for every file below `path` in a stable order.
+fn visit_files(
+    path: &Path,
+    relative_path: &str,
+    visit: &mut dyn FnMut(&str, File) -> Result<(), Error>,
+) -> Result<(), Error> {
+    if !path.is_dir() {
+        return visit(relative_path, File::open(path)?);
+    }
+
+    let mut entries = fs::read_dir(path)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let relative_path = if relative_path.is_empty() {
+            name.into_owned()
+        } else {
+            format!("{relative_path}/{name}")
+        };
+
+        // symlinks to directories are skipped, they could form a cycle
+        if entry.file_type()?.is_symlink() && entry.path().is_dir() {
+            continue;
+        }
+
+        visit_files(&entry.path(), &relative_path, visit)?;
+    }
+
+    Ok(())
+}
+
+fn read_chunks(mut file: File, mut consume: impl FnMut(&[u8])) -> Result<(), Error> {
+    let mut buffer = vec![0; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+
+        if read == 0 {
+            return Ok(());
+        }
+
+        consume(&buffer[..read]);
+    }
+}
@@ -0,0 +1,172 @@
+use std::{fmt::Display, string::FromUtf8Error, time::SystemTimeError};
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::async_logging::AsyncLoggingError;
+
+/// Error codes that are returned by the MLflow server.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String")]
+pub enum ErrorCode {
+    InternalError,
+    TemporarilyUnavailable,
+    IoError,
+    BadRequest,
+    InvalidParameterValue,
+    EndpointNotFound,
+    MalformedRequest,
+    InvalidState,
+    PermissionDenied,
+    FeatureDisabled,
+    Unauthenticated,
+    RequestLimitExceeded,
+    ResourceAlreadyExists,
+    ResourceDoesNotExist,
+    ResourceExhausted,
+    ResourceConflict,
+    NotImplemented,
+    Other(String),
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "INTERNAL_ERROR" => Self::InternalError,
+            "TEMPORARILY_UNAVAILABLE" => Self::TemporarilyUnavailable,
+            "IO_ERROR" => Self::IoError,
+            "BAD_REQUEST" => Self::BadRequest,
+            "INVALID_PARAMETER_VALUE" => Self::InvalidParameterValue,
+            "ENDPOINT_NOT_FOUND" => Self::EndpointNotFound,
+            "MALFORMED_REQUEST" => Self::MalformedRequest,
+            "INVALID_STATE" => Self::InvalidState,
+            "PERMISSION_DENIED" => Self::PermissionDenied,
+            "FEATURE_DISABLED" => Self::FeatureDisabled,
+            "UNAUTHENTICATED" => Self::Unauthenticated,
+            "REQUEST_LIMIT_EXCEEDED" => Self::RequestLimitExceeded,
+            "RESOURCE_ALREADY_EXISTS" => Self::ResourceAlreadyExists,
+            "RESOURCE_DOES_NOT_EXIST" => Self::ResourceDoesNotExist,
+            "RESOURCE_EXHAUSTED" => Self::ResourceExhausted,
+            "RESOURCE_CONFLICT" => Self::ResourceConflict,
+            "NOT_IMPLEMENTED" => Self::NotImplemented,
+            _ => Self::Other(code),
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::InternalError => "INTERNAL_ERROR",
+            Self::TemporarilyUnavailable => "TEMPORARILY_UNAVAILABLE",
+            Self::IoError => "IO_ERROR",
+            Self::BadRequest => "BAD_REQUEST",
+            Self::InvalidParameterValue => "INVALID_PARAMETER_VALUE",
+            Self::EndpointNotFound => "ENDPOINT_NOT_FOUND",
+            Self::MalformedRequest => "MALFORMED_REQUEST",
+            Self::InvalidState => "INVALID_STATE",
+            Self::PermissionDenied => "PERMISSION_DENIED",
+            Self::FeatureDisabled => "FEATURE_DISABLED",
+            Self::Unauthenticated => "UNAUTHENTICATED",
+            Self::RequestLimitExceeded => "REQUEST_LIMIT_EXCEEDED",
+            Self::ResourceAlreadyExists => "RESOURCE_ALREADY_EXISTS",
+            Self::ResourceDoesNotExist => "RESOURCE_DOES_NOT_EXIST",
+            Self::ResourceExhausted => "RESOURCE_EXHAUSTED",
+            Self::ResourceConflict => "RESOURCE_CONFLICT",
+            Self::NotImplemented => "NOT_IMPLEMENTED",
+            Self::Other(code) => code,
+        };
+
+        f.write_str(code)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("the tracking server returned {error_code} ({status}): {message}")]
+    Server {
+        status: StatusCode,
+        error_code: ErrorCode,
+        message: String,
+    },
+    #[error("could not (de)serialize JSON: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("could not (de)serialize YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("could not (de)serialize binary data: {0}")]
+    BinarySerialization(#[from] bincode::Error),
+    #[error("data is not valid UTF-8: {0}")]
+    Utf8(#[from] FromUtf8Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    #[error("The repository contains uncommitted changes.")]
+    DirtyRepo,
+    #[error("The repository contains one or more submodules which is currently not supported.")]
+    RepoContainsSubmodules,
+    #[error("The repository contains one or more subfolder which themselves container git repos which is currently not supported.")]
+    RepoContainsSubfolderRepos,
+    #[error("git failed: {0}")]
+    Git(String),
+    #[error("the experiment {0} exists in a deleted state")]
+    DeletedExperiment(String),
+    #[error("version {version} of the model {name} could not be registered: {message}")]
+    ModelRegistration {
+        name: String,
+        version: String,
+        message: String,
+    },
+    #[error("This operation is not possible when experiment tracking is disabled.")]
+    TrackingDisabled,
+    #[error(transparent)]
+    AsyncLogging(#[from] AsyncLoggingError),
+    #[error(transparent)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    /// The HTTP status of the response that caused the error, if there was one.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Http(e) => e.status(),
+            Self::Server { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    pub fn error_code(&self) -> Option<&ErrorCode> {
+        match self {
+            Self::Server { error_code, .. } => Some(error_code),
+            _ => None,
+        }
+    }
+
+    /// The message of the server for server errors, otherwise the description of the error.
+    pub fn message(&self) -> String {
+        match self {
+            Self::Server { message, .. } => message.clone(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl From<SystemTimeError> for Error {
+    fn from(e: SystemTimeError) -> Self {
+        Self::Other(Box::new(e))
+    }
+}
+
+impl From<log::SetLoggerError> for Error {
+    fn from(e: log::SetLoggerError) -> Self {
+        Self::Other(Box::new(e))
+    }
+}
+
+impl From<ctrlc::Error> for Error {
+    fn from(e: ctrlc::Error) -> Self {
+        Self::Other(Box::new(e))
+    }
+}
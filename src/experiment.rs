@@ -1,156 +1,347 @@
-use std::{error::Error, fmt::Display, time::SystemTime};
+use std::time::SystemTime;
 
 use log::error;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    client::MlflowClient,
+    error::{Error, ErrorCode},
     git_utils::{
         create_diff, does_repo_contain_subfolders_with_repos, does_repo_contain_submodules,
         get_commit_hash, is_repo_clean,
     },
-    run::{Run, RunTag},
+    run::{Run, RunTag, SearchRunsOptions},
     schemas::{
         CreateExperimentRequest, CreateExperimentResponse, CreateRunRequest, CreateRunResponse,
-        GetExperimentByNameRequest, GetExperimentRequest, GetExperimentResponse,
+        DeleteExperimentRequest, DeleteExperimentResponse, DeleteExperimentTagRequest,
+        DeleteExperimentTagResponse, GetExperimentByNameRequest, GetExperimentRequest,
+        GetExperimentResponse, RestoreExperimentRequest, RestoreExperimentResponse,
+        SearchExperimentsRequest, SearchExperimentsResponse, SetExperimentTagRequest,
+        SetExperimentTagResponse, UpdateExperimentRequest, UpdateExperimentResponse,
     },
-    utils::{checked_get_request, checked_post_request},
+    utils::Paginated,
 };
 
-#[derive(Debug)]
-pub struct DirtyRepoError {}
-
-impl Display for DirtyRepoError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "The repository contains uncommitted changes.")
-    }
+/// Selects experiments / runs by their lifecycle stage.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ViewType {
+    #[default]
+    ActiveOnly,
+    DeletedOnly,
+    All,
 }
 
-impl Error for DirtyRepoError {}
-
-#[derive(Debug)]
-pub struct RepoContainsSubmodulesError {}
-
-impl Display for RepoContainsSubmodulesError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "The repository contains one or more submodules which is currently not supported."
-        )
-    }
+#[derive(Clone, Debug, Default)]
+pub struct SearchExperimentsOptions {
+    /// e.g. `name LIKE 'project-%' AND tags.team = 'research'`
+    pub filter: Option<String>,
+    /// e.g. `name ASC` or `last_update_time DESC`
+    pub order_by: Vec<String>,
+    pub view_type: ViewType,
+    /// Number of experiments that are requested at once. The server default is used if not set.
+    pub page_size: Option<u32>,
 }
 
-impl Error for RepoContainsSubmodulesError {}
-
-#[derive(Debug)]
-pub struct RepoContainsSubfolderReposError {}
-
-impl Display for RepoContainsSubfolderReposError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "The repository contains one or more subfolder which themselves container git repos which is currently not supported.")
-    }
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExperimentTag {
+    pub key: String,
+    pub value: String,
 }
 
-impl Error for RepoContainsSubfolderReposError {}
-
 #[derive(Deserialize, Default)]
 pub struct Experiment {
     #[serde(skip)]
-    api_root: String,
+    client: MlflowClient,
     experiment_id: String,
     name: String,
     artifact_location: String,
     lifecycle_stage: String,
     last_update_time: u64,
     creation_time: u64,
+    #[serde(default)]
+    tags: Vec<ExperimentTag>,
 }
 
 impl Experiment {
+    pub fn new(client: &MlflowClient, name: &str) -> Result<Self, Error> {
+        Self::new_with_tags(client, name, vec![])
+    }
+
     #[cfg(not(disable_experiment_tracking))]
-    pub fn new(api_root: &str, name: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let response: CreateExperimentResponse = checked_post_request(
-            &format!("{api_root}/api/2.0/mlflow/experiments/create"),
+    pub fn new_with_tags(
+        client: &MlflowClient,
+        name: &str,
+        tags: Vec<ExperimentTag>,
+    ) -> Result<Self, Error> {
+        let response: CreateExperimentResponse = client.checked_post_request(
+            "api/2.0/mlflow/experiments/create",
             &CreateExperimentRequest {
                 name: name.to_owned(),
-                tags: vec![],
+                tags,
             },
         ).map_err(|err| {
             error!("an experiment with the name {} might exist already or still exists in a deleted state.", name);
             err
     })?;
-        Self::search_with_id(api_root, &response.experiment_id)
+        Self::search_with_id(client, &response.experiment_id)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn new(_: &str, _: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new_with_tags(_: &MlflowClient, _: &str, _: Vec<ExperimentTag>) -> Result<Self, Error> {
         Ok(Self::default())
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn search_with_id(api_root: &str, id: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let response: GetExperimentResponse = checked_get_request(
-            &format!("{api_root}/api/2.0/mlflow/experiments/get"),
+    pub fn search_with_id(client: &MlflowClient, id: &str) -> Result<Self, Error> {
+        let response: GetExperimentResponse = client.checked_get_request(
+            "api/2.0/mlflow/experiments/get",
             &GetExperimentRequest {
                 experiment_id: id.to_owned(),
             },
         )?;
 
         let mut experiment = response.experiment;
-        experiment.api_root = api_root.to_owned();
+        experiment.client = client.clone();
 
         Ok(experiment)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn search_with_id(_: &str, _: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn search_with_id(_: &MlflowClient, _: &str) -> Result<Self, Error> {
         Ok(Self::default())
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn search_with_name(
-        api_root: &str,
-        name: &str,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        let response: GetExperimentResponse = checked_get_request(
-            &format!("{api_root}/api/2.0/mlflow/experiments/get-by-name"),
+    pub fn search_with_name(client: &MlflowClient, name: &str) -> Result<Self, Error> {
+        let response: GetExperimentResponse = client.checked_get_request(
+            "api/2.0/mlflow/experiments/get-by-name",
             &GetExperimentByNameRequest {
                 experiment_name: name.to_owned(),
             },
         )?;
 
         let mut experiment = response.experiment;
-        experiment.api_root = api_root.to_owned();
+        experiment.client = client.clone();
+
+        Ok(experiment)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn search_with_name(_: &MlflowClient, _: &str) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    /// Returns all experiments matching the options. Further pages are requested while iterating.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn search(
+        client: &MlflowClient,
+        options: SearchExperimentsOptions,
+    ) -> impl Iterator<Item = Result<Self, Error>> {
+        let client = client.clone();
+
+        Paginated::new(move |page_token| {
+            let response: SearchExperimentsResponse = client.checked_post_request(
+                "api/2.0/mlflow/experiments/search",
+                &SearchExperimentsRequest {
+                    max_results: options.page_size,
+                    page_token,
+                    filter: options.filter.clone(),
+                    order_by: options.order_by.clone(),
+                    view_type: options.view_type,
+                },
+            )?;
+
+            let mut experiments = response.experiments;
+
+            for experiment in &mut experiments {
+                experiment.client = client.clone();
+            }
+
+            Ok((experiments, response.next_page_token))
+        })
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn search(
+        _: &MlflowClient,
+        _: SearchExperimentsOptions,
+    ) -> impl Iterator<Item = Result<Self, Error>> {
+        std::iter::empty()
+    }
+
+    /// Returns the experiment with the given name and creates it if it does not exist yet.
+    /// An experiment in the deleted state is restored if `restore_deleted` is true, otherwise an error is returned.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn get_or_create(
+        client: &MlflowClient,
+        name: &str,
+        restore_deleted: bool,
+    ) -> Result<Self, Error> {
+        let mut experiment = match Self::search_with_name(client, name) {
+            Ok(experiment) => experiment,
+            Err(e) if e.error_code() == Some(&ErrorCode::ResourceDoesNotExist) => {
+                let response = client.checked_post_request::<_, CreateExperimentResponse>(
+                    "api/2.0/mlflow/experiments/create",
+                    &CreateExperimentRequest {
+                        name: name.to_owned(),
+                        tags: vec![],
+                    },
+                );
+
+                match response {
+                    Ok(response) => return Self::search_with_id(client, &response.experiment_id),
+                    // another process created the experiment in the meantime
+                    Err(e) if e.error_code() == Some(&ErrorCode::ResourceAlreadyExists) => {
+                        Self::search_with_name(client, name)?
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if experiment.lifecycle_stage != "deleted" {
+            return Ok(experiment);
+        }
+
+        if !restore_deleted {
+            return Err(Error::DeletedExperiment(name.to_owned()));
+        }
+
+        experiment.restore()?;
 
         Ok(experiment)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn search_with_name(_: &str, _: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn get_or_create(_: &MlflowClient, _: &str, _: bool) -> Result<Self, Error> {
         Ok(Self::default())
     }
 
+    /// Moves the experiment and its runs into the deleted state. They can be restored until they are garbage collected.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete(&mut self) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<DeleteExperimentRequest, DeleteExperimentResponse>(
+                "api/2.0/mlflow/experiments/delete",
+                &DeleteExperimentRequest {
+                    experiment_id: self.experiment_id.clone(),
+                },
+            )?;
+
+        self.refresh()
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn restore(&mut self) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<RestoreExperimentRequest, RestoreExperimentResponse>(
+                "api/2.0/mlflow/experiments/restore",
+                &RestoreExperimentRequest {
+                    experiment_id: self.experiment_id.clone(),
+                },
+            )?;
+
+        self.refresh()
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn restore(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn rename(&mut self, new_name: &str) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<UpdateExperimentRequest, UpdateExperimentResponse>(
+                "api/2.0/mlflow/experiments/update",
+                &UpdateExperimentRequest {
+                    experiment_id: self.experiment_id.clone(),
+                    new_name: new_name.to_owned(),
+                },
+            )?;
+
+        self.refresh()
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn rename(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    fn refresh(&mut self) -> Result<(), Error> {
+        *self = Self::search_with_id(&self.client, &self.experiment_id)?;
+
+        Ok(())
+    }
+
+    /// Adds the tag or overwrites its value if it exists already.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn set_tag(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<SetExperimentTagRequest, SetExperimentTagResponse>(
+                "api/2.0/mlflow/experiments/set-experiment-tag",
+                &SetExperimentTagRequest {
+                    experiment_id: self.experiment_id.clone(),
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                },
+            )?;
+
+        match self.tags.iter_mut().find(|tag| tag.key == key) {
+            Some(tag) => tag.value = value.to_owned(),
+            None => self.tags.push(ExperimentTag {
+                key: key.to_owned(),
+                value: value.to_owned(),
+            }),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn set_tag(&mut self, _: &str, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete_tag(&mut self, key: &str) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<DeleteExperimentTagRequest, DeleteExperimentTagResponse>(
+                "api/2.0/mlflow/experiments/delete-experiment-tag",
+                &DeleteExperimentTagRequest {
+                    experiment_id: self.experiment_id.clone(),
+                    key: key.to_owned(),
+                },
+            )?;
+
+        self.tags.retain(|tag| tag.key != key);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete_tag(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
     #[cfg(not(disable_experiment_tracking))]
     fn create_run_unchecked(
         &self,
         run_name: Option<&str>,
         mut tags: Vec<RunTag>,
-    ) -> Result<Run, Box<dyn std::error::Error>> {
-        use std::env;
-
-        tags.push(RunTag {
-            key: "mlflow.source.git.commit".to_owned(),
-            value: get_commit_hash()?,
-        });
-
-        let args: Vec<String> = env::args().collect();
-        let args = args.join(" ");
+    ) -> Result<Run, Error> {
+        add_source_tags(&mut tags)?;
 
-        tags.push(RunTag {
-            key: "cmd".to_owned(),
-            value: args,
-        });
-
-        let response: CreateRunResponse = checked_post_request(
-            &format!("{}/api/2.0/mlflow/runs/create", self.api_root),
+        let response: CreateRunResponse = self.client.checked_post_request(
+            "api/2.0/mlflow/runs/create",
             &CreateRunRequest {
                 experiment_id: self.experiment_id.clone(),
                 run_name: run_name.map(|x| x.to_owned()),
@@ -162,38 +353,24 @@ impl Experiment {
         )?;
 
         let mut run = response.run;
-        run.set_api_root(&self.api_root);
+        run.set_client(&self.client);
 
         Ok(run)
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn create_run(
-        &self,
-        run_name: Option<&str>,
-        tags: Vec<RunTag>,
-    ) -> Result<Run, Box<dyn std::error::Error>> {
-        if does_repo_contain_submodules()? {
-            Err(RepoContainsSubmodulesError {})?
-        }
-
-        if does_repo_contain_subfolders_with_repos()? {
-            Err(RepoContainsSubfolderReposError {})?
-        }
+    pub fn create_run(&self, run_name: Option<&str>, tags: Vec<RunTag>) -> Result<Run, Error> {
+        check_repo_is_supported()?;
 
         if !is_repo_clean()? {
-            Err(DirtyRepoError {})?
+            Err(Error::DirtyRepo)?
         }
 
         self.create_run_unchecked(run_name, tags)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn create_run(
-        &self,
-        _: Option<&str>,
-        _: Vec<RunTag>,
-    ) -> Result<Run, Box<dyn std::error::Error>> {
+    pub fn create_run(&self, _: Option<&str>, _: Vec<RunTag>) -> Result<Run, Error> {
         Ok(Run::default())
     }
 
@@ -202,14 +379,8 @@ impl Experiment {
         &self,
         run_name: Option<&str>,
         tags: Vec<RunTag>,
-    ) -> Result<Run, Box<dyn std::error::Error>> {
-        if does_repo_contain_submodules()? {
-            Err(RepoContainsSubmodulesError {})?
-        }
-
-        if does_repo_contain_subfolders_with_repos()? {
-            Err(RepoContainsSubfolderReposError {})?
-        }
+    ) -> Result<Run, Error> {
+        check_repo_is_supported()?;
 
         let run = self.create_run_unchecked(run_name, tags)?;
 
@@ -223,15 +394,26 @@ impl Experiment {
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn create_run_with_git_diff(
-        &self,
-        _: Option<&str>,
-        _: Vec<RunTag>,
-    ) -> Result<Run, Box<dyn std::error::Error>> {
+    pub fn create_run_with_git_diff(&self, _: Option<&str>, _: Vec<RunTag>) -> Result<Run, Error> {
         Ok(Run::default())
     }
 
-    // TODO: search run
+    /// Returns the runs of this experiment matching the options, see [`Run::search`].
+    pub fn search_runs(
+        &self,
+        options: SearchRunsOptions,
+    ) -> impl Iterator<Item = Result<Run, Error>> {
+        Run::search(&self.client, &[&self.experiment_id], options)
+    }
+
+    /// Deletes the runs of this experiment matching the options, see [`Run::delete_matching`].
+    pub fn delete_runs(
+        &self,
+        options: SearchRunsOptions,
+        dry_run: bool,
+    ) -> Result<Vec<Run>, Error> {
+        Run::delete_matching(&self.client, &[&self.experiment_id], options, dry_run)
+    }
 
     pub fn get_experiment_id(&self) -> &str {
         &self.experiment_id
@@ -256,4 +438,40 @@ impl Experiment {
     pub fn get_creating_time(&self) -> u64 {
         self.creation_time
     }
+
+    pub fn get_tags(&self) -> &Vec<ExperimentTag> {
+        &self.tags
+    }
+}
+
+pub(crate) fn check_repo_is_supported() -> Result<(), Error> {
+    if does_repo_contain_submodules()? {
+        Err(Error::RepoContainsSubmodules)?
+    }
+
+    if does_repo_contain_subfolders_with_repos()? {
+        Err(Error::RepoContainsSubfolderRepos)?
+    }
+
+    Ok(())
+}
+
+/// Adds the tags that describe where the run was started from.
+pub(crate) fn add_source_tags(tags: &mut Vec<RunTag>) -> Result<(), Error> {
+    use std::env;
+
+    tags.push(RunTag {
+        key: "mlflow.source.git.commit".to_owned(),
+        value: get_commit_hash()?,
+    });
+
+    let args: Vec<String> = env::args().collect();
+    let args = args.join(" ");
+
+    tags.push(RunTag {
+        key: "cmd".to_owned(),
+        value: args,
+    });
+
+    Ok(())
 }
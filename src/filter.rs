@@ -0,0 +1,157 @@
+use crate::error::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparator {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    /// case sensitive pattern matching with `%` as wildcard
+    Like,
+    /// case insensitive pattern matching with `%` as wildcard
+    ILike,
+}
+
+impl Comparator {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Equal => "=",
+            Self::NotEqual => "!=",
+            Self::GreaterThan => ">",
+            Self::GreaterThanOrEqual => ">=",
+            Self::LessThan => "<",
+            Self::LessThanOrEqual => "<=",
+            Self::Like => "LIKE",
+            Self::ILike => "ILIKE",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterValue {
+    Number(f64),
+    String(String),
+}
+
+impl From<f64> for FilterValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<i64> for FilterValue {
+    fn from(value: i64) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+/// Builds a filter string for the run search where all conditions have to be true.
+///
+/// ```
+/// use mlflow_rs::filter::{Comparator, RunFilter};
+///
+/// let filter = RunFilter::new()
+///     .metric("loss", Comparator::LessThan, 0.1)
+///     .param("lr", Comparator::Equal, "0.01")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(filter, "metrics.`loss` < 0.1 AND params.`lr` = '0.01'");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RunFilter {
+    conditions: Vec<(&'static str, String, Comparator, FilterValue)>,
+}
+
+impl RunFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn metric(self, key: &str, comparator: Comparator, value: f64) -> Self {
+        self.condition("metrics", key, comparator, value.into())
+    }
+
+    pub fn param(self, key: &str, comparator: Comparator, value: &str) -> Self {
+        self.condition("params", key, comparator, value.into())
+    }
+
+    pub fn tag(self, key: &str, comparator: Comparator, value: &str) -> Self {
+        self.condition("tags", key, comparator, value.into())
+    }
+
+    /// Attributes of the run like `status`, `run_name`, `start_time` or `end_time`.
+    pub fn attribute<V: Into<FilterValue>>(
+        self,
+        key: &str,
+        comparator: Comparator,
+        value: V,
+    ) -> Self {
+        self.condition("attributes", key, comparator, value.into())
+    }
+
+    fn condition(
+        mut self,
+        entity: &'static str,
+        key: &str,
+        comparator: Comparator,
+        value: FilterValue,
+    ) -> Self {
+        self.conditions
+            .push((entity, key.to_owned(), comparator, value));
+        self
+    }
+
+    pub fn build(&self) -> Result<String, Error> {
+        let mut conditions = Vec::with_capacity(self.conditions.len());
+
+        for (entity, key, comparator, value) in &self.conditions {
+            conditions.push(format!(
+                "{entity}.{} {} {}",
+                quote_key(key)?,
+                comparator.as_str(),
+                format_value(value)?
+            ));
+        }
+
+        Ok(conditions.join(" AND "))
+    }
+}
+
+fn quote_key(key: &str) -> Result<String, Error> {
+    if key.contains('`') {
+        return Err(Error::InvalidInput(format!(
+            "the key {key} contains a backtick which is not supported in filters"
+        )));
+    }
+
+    Ok(format!("`{key}`"))
+}
+
+fn format_value(value: &FilterValue) -> Result<String, Error> {
+    match value {
+        FilterValue::Number(number) if number.is_finite() => Ok(number.to_string()),
+        FilterValue::Number(number) => Err(Error::InvalidInput(format!(
+            "{number} cannot be used in a filter"
+        ))),
+        FilterValue::String(string) if !string.contains('\'') => Ok(format!("'{string}'")),
+        FilterValue::String(string) if !string.contains('"') => Ok(format!("\"{string}\"")),
+        FilterValue::String(string) => Err(Error::InvalidInput(format!(
+            "the value {string} contains single and double quotes which is not supported in filters"
+        ))),
+    }
+}
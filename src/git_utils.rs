@@ -1,31 +1,49 @@
-use std::{error::Error, process::Command};
+use std::{fmt::Display, process::Command};
 
-pub(crate) fn get_commit_hash() -> Result<String, Box<dyn Error>> {
-    let output = Command::new("git").arg("rev-parse").arg("HEAD").output()?;
-    let converted = String::from_utf8(output.stdout)?.trim().to_owned();
+use crate::error::Error;
+
+fn git_error(e: impl Display) -> Error {
+    Error::Git(e.to_string())
+}
+
+pub(crate) fn get_commit_hash() -> Result<String, Error> {
+    let output = Command::new("git")
+        .arg("rev-parse")
+        .arg("HEAD")
+        .output()
+        .map_err(git_error)?;
+    let converted = String::from_utf8(output.stdout)
+        .map_err(git_error)?
+        .trim()
+        .to_owned();
 
     Ok(converted)
 }
 
-pub(crate) fn is_repo_clean() -> Result<bool, Box<dyn Error>> {
+pub(crate) fn is_repo_clean() -> Result<bool, Error> {
     let output = Command::new("git")
         .arg("status")
         .arg("--porcelain")
-        .output()?;
-    let converted = String::from_utf8(output.stdout)?.trim().to_owned();
+        .output()
+        .map_err(git_error)?;
+    let converted = String::from_utf8(output.stdout)
+        .map_err(git_error)?
+        .trim()
+        .to_owned();
 
     let is_clean = converted == "";
 
     Ok(is_clean)
 }
 
-pub(crate) fn create_diff() -> Result<Vec<u8>, Box<dyn Error>> {
+pub(crate) fn create_diff() -> Result<Vec<u8>, Error> {
     // Create a stash including untracked files
     Command::new("git")
         .arg("stash")
         .arg("push")
         .arg("-u")
-        .output()?;
+        .output()
+        .map_err(git_error)?;
 
     // Create diff from stash
     let diff = Command::new("git")
@@ -40,44 +58,57 @@ pub(crate) fn create_diff() -> Result<Vec<u8>, Box<dyn Error>> {
         .arg("stash")
         .arg("pop")
         .arg("--index")
-        .output()?;
+        .output()
+        .map_err(git_error)?;
 
-    Ok(diff?.stdout)
+    Ok(diff.map_err(git_error)?.stdout)
 }
 
-pub(crate) fn does_repo_contain_submodules() -> Result<bool, Box<dyn Error>> {
+pub(crate) fn does_repo_contain_submodules() -> Result<bool, Error> {
     let output = Command::new("git")
         .arg("submodule")
         .arg("status")
         .arg("--recursive")
-        .output()?;
+        .output()
+        .map_err(git_error)?;
 
-    let converted = String::from_utf8(output.stdout)?.trim().to_owned();
+    let converted = String::from_utf8(output.stdout)
+        .map_err(git_error)?
+        .trim()
+        .to_owned();
 
     Ok(converted != "")
 }
 
-fn get_repo_root() -> Result<String, Box<dyn Error>> {
+fn get_repo_root() -> Result<String, Error> {
     let output = Command::new("git")
         .arg("rev-parse")
         .arg("--show-toplevel")
-        .output()?;
+        .output()
+        .map_err(git_error)?;
 
-    let converted = String::from_utf8(output.stdout)?.trim().to_owned();
+    let converted = String::from_utf8(output.stdout)
+        .map_err(git_error)?
+        .trim()
+        .to_owned();
 
     Ok(converted)
 }
 
-pub(crate) fn does_repo_contain_subfolders_with_repos() -> Result<bool, Box<dyn Error>> {
+pub(crate) fn does_repo_contain_subfolders_with_repos() -> Result<bool, Error> {
     let root_path = get_repo_root()?;
 
     let output = Command::new("find")
         .arg(root_path)
         .arg("-name")
         .arg(".git")
-        .output()?;
+        .output()
+        .map_err(git_error)?;
 
-    let converted = String::from_utf8(output.stdout)?.trim().to_owned();
+    let converted = String::from_utf8(output.stdout)
+        .map_err(git_error)?
+        .trim()
+        .to_owned();
     let git_repo_paths: Vec<&str> = converted.lines().collect();
 
     Ok(git_repo_paths.len() > 1)
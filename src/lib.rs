@@ -1,8 +1,21 @@
 #![cfg_attr(disable_experiment_tracking, allow(unused))] // disables warning about unused code when experiment tracking is disabled
 
+pub mod artifacts;
+pub mod async_logging;
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod client;
+pub mod dataset;
+mod error;
 pub mod experiment;
+pub mod filter;
 mod git_utils;
 pub mod logger;
+pub mod model;
+pub mod offline;
+pub mod registry;
 pub mod run;
 mod schemas;
 pub mod utils;
+
+pub use error::{Error, ErrorCode};
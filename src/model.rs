@@ -0,0 +1,43 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Name of the descriptor file in the artifact directory of a model.
+pub const MLMODEL_FILE_NAME: &str = "MLmodel";
+
+/// Content of the `MLmodel` file that describes how a model can be loaded.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ModelDescriptor {
+    pub artifact_path: String,
+    /// Configuration of each flavor by its name, e.g. `{"rust": {"format": "bincode", "data": "model.bin"}}`.
+    pub flavors: BTreeMap<String, Value>,
+    pub run_id: String,
+    /// e.g. `2024-01-31 12:00:00.000000`
+    pub utc_time_created: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ModelSignature>,
+    /// Fields that are not known to this crate, e.g. written by the python client.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl ModelDescriptor {
+    pub fn to_yaml(&self) -> Result<String, crate::Error> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, crate::Error> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+}
+
+/// Inputs and outputs of the model as JSON encoded column or tensor specs,
+/// e.g. `[{"type": "tensor", "tensor-spec": {"dtype": "float32", "shape": [-1, 4]}}]`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ModelSignature {
+    pub inputs: Option<String>,
+    pub outputs: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<String>,
+}
@@ -0,0 +1,1023 @@
+use std::{
+    collections::hash_map::RandomState,
+    fs::{self, File, OpenOptions},
+    hash::{BuildHasher, Hasher},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use log::warn;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    client::MlflowClient,
+    dataset::DatasetInput,
+    error::{Error, ErrorCode},
+    experiment::{add_source_tags, check_repo_is_supported, Experiment, ViewType},
+    filter::{Comparator, RunFilter},
+    git_utils::{create_diff, is_repo_clean},
+    model::{ModelDescriptor, MLMODEL_FILE_NAME},
+    run::{
+        Metric, Run, RunInfo, RunParameters, RunTag, SearchRunsOptions, Status, LOGGED_MODELS_TAG,
+    },
+    schemas::{CreateRunRequest, CreateRunResponse, UpdateRunRequest, UpdateRunResponse},
+};
+
+/// Tag of a synchronized run that holds the id of its offline run, so it is not created twice.
+pub const OFFLINE_RUN_ID_TAG: &str = "mlflow_rs.offline_run_id";
+
+const JOURNAL_FILE_NAME: &str = "journal.jsonl";
+const SYNC_STATE_FILE_NAME: &str = "sync.json";
+const ARTIFACTS_DIR_NAME: &str = "artifacts";
+// the sync state is saved after each batch, so an interrupted sync only repeats the last one
+const MAX_ENTRIES_PER_BATCH: usize = 1000;
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum JournalEntry {
+    CreateRun {
+        experiment_name: String,
+        run_name: Option<String>,
+        start_time: u64,
+        tags: Vec<RunTag>,
+    },
+    Metric(Metric),
+    Parameter(RunParameters),
+    Tag(RunTag),
+    DeleteTag {
+        key: String,
+    },
+    Input(DatasetInput),
+    /// The file is stored at the same path in the artifact directory of the journal.
+    Artifact {
+        path: String,
+    },
+    EndRun {
+        status: Status,
+        end_time: u64,
+    },
+}
+
+/// Operations of an offline run, one JSON object per line.
+pub(crate) struct Journal {
+    dir: PathBuf,
+    file: Mutex<File>,
+}
+
+impl Journal {
+    fn open(dir: &Path) -> Result<Self, Error> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(JOURNAL_FILE_NAME))?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    pub(crate) fn append(&self, entry: &JournalEntry) -> Result<(), Error> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        // the file stays usable if another thread panicked while writing
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        // a single write keeps lines of concurrent appends apart
+        file.write_all(&line)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn artifacts_dir(&self) -> PathBuf {
+        self.dir.join(ARTIFACTS_DIR_NAME)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SyncSummary {
+    pub synced: Vec<SyncedRun>,
+    /// Local ids of the runs that could not be synchronized completely.
+    pub failed: Vec<(String, Error)>,
+}
+
+impl SyncSummary {
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty() && self.synced.iter().all(|run| run.failed_entries.is_empty())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SyncedRun {
+    pub local_run_id: String,
+    pub server_run_id: String,
+    /// Number of journal entries that were sent by this synchronization, 0 if the run was up to date.
+    pub replayed_entries: usize,
+    /// Line numbers of the journal entries that the server rejected, they are not sent again.
+    pub failed_entries: Vec<(usize, Error)>,
+}
+
+/// Progress of the synchronization of a journal, stored next to it.
+#[derive(Serialize, Deserialize, Default)]
+struct SyncState {
+    server_run_id: Option<String>,
+    synced_entries: usize,
+}
+
+#[derive(Default)]
+struct Batch {
+    metrics: Vec<Metric>,
+    params: Vec<RunParameters>,
+    tags: Vec<RunTag>,
+}
+
+impl Batch {
+    fn len(&self) -> usize {
+        self.metrics.len() + self.params.len() + self.tags.len()
+    }
+
+    fn send(&mut self, run: &Run) -> Result<(), Error> {
+        if self.len() > 0 {
+            run.log_batch(&self.metrics, &self.params, &self.tags)?;
+        }
+
+        *self = Self::default();
+
+        Ok(())
+    }
+}
+
+/// Creates a run that is only recorded in a new subdirectory of `journal_dir` until it is synchronized with [`sync`].
+/// Metrics, parameters, tags, dataset inputs, artifacts and the end of the run are appended to the journal.
+/// Like [`Experiment::create_run_with_git_diff`] uncommitted changes are stored as the artifact `uncommitted.patch`.
+#[cfg(not(disable_experiment_tracking))]
+pub fn create_run(
+    journal_dir: &Path,
+    experiment_name: &str,
+    run_name: Option<&str>,
+    mut tags: Vec<RunTag>,
+) -> Result<Run, Error> {
+    check_repo_is_supported()?;
+    add_source_tags(&mut tags)?;
+
+    let start_time = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_millis() as u64;
+    let run_id = format!(
+        "offline-{start_time:x}-{:016x}",
+        RandomState::new().build_hasher().finish()
+    );
+
+    fs::create_dir_all(journal_dir)?;
+    let run_dir = journal_dir.canonicalize()?.join(&run_id);
+    // fails instead of mixing two runs if the id exists already
+    fs::create_dir(&run_dir)?;
+    fs::create_dir(run_dir.join(ARTIFACTS_DIR_NAME))?;
+
+    let journal = Journal::open(&run_dir)?;
+    journal.append(&JournalEntry::CreateRun {
+        experiment_name: experiment_name.to_owned(),
+        run_name: run_name.map(|x| x.to_owned()),
+        start_time,
+        tags: tags.clone(),
+    })?;
+
+    let artifact_uri = Url::from_directory_path(journal.artifacts_dir())
+        .map_err(|_| {
+            Error::InvalidInput(format!("{} is not a valid directory", run_dir.display()))
+        })?
+        .to_string();
+
+    let info = RunInfo {
+        run_uuid: run_id.clone(),
+        experiment_id: String::new(),
+        run_name: run_name.unwrap_or_default().to_owned(),
+        user_id: String::new(),
+        status: "RUNNING".to_owned(),
+        start_time,
+        artifact_uri,
+        lifecycle_stage: "active".to_owned(),
+        run_id,
+    };
+    let run = Run::new_offline(info, tags, journal);
+
+    if !is_repo_clean()? {
+        run.log_artifact_bytes(create_diff()?, "uncommitted.patch")?;
+    }
+
+    Ok(run)
+}
+
+#[cfg(disable_experiment_tracking)]
+pub fn create_run(_: &Path, _: &str, _: Option<&str>, _: Vec<RunTag>) -> Result<Run, Error> {
+    Ok(Run::default())
+}
+
+/// Sends all offline runs in `journal_dir` to the server. A failed run does not stop the others.
+/// Only the entries that were not sent by an earlier synchronization are sent,
+/// so it is safe to synchronize again after an interruption or while a run is still logging.
+#[cfg(not(disable_experiment_tracking))]
+pub fn sync(client: &MlflowClient, journal_dir: &Path) -> Result<SyncSummary, Error> {
+    let mut entries = fs::read_dir(journal_dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut summary = SyncSummary::default();
+
+    for entry in entries {
+        if !entry.path().join(JOURNAL_FILE_NAME).is_file() {
+            continue;
+        }
+
+        match sync_run(client, &entry.path()) {
+            Ok(synced_run) => summary.synced.push(synced_run),
+            Err(e) => summary
+                .failed
+                .push((entry.file_name().to_string_lossy().into_owned(), e)),
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(disable_experiment_tracking)]
+pub fn sync(_: &MlflowClient, _: &Path) -> Result<SyncSummary, Error> {
+    Ok(SyncSummary::default())
+}
+
+/// Sends the offline run in `run_dir` to the server, see [`sync`].
+#[cfg(not(disable_experiment_tracking))]
+pub fn sync_run(client: &MlflowClient, run_dir: &Path) -> Result<SyncedRun, Error> {
+    let local_run_id = run_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let artifacts_dir = run_dir.join(ARTIFACTS_DIR_NAME);
+    let mut state = read_state(run_dir)?;
+    let mut server_run = match &state.server_run_id {
+        Some(server_run_id) => Some(Run::get_run(client, server_run_id)?),
+        None => None,
+    };
+    let mut reader = BufReader::new(File::open(run_dir.join(JOURNAL_FILE_NAME))?);
+    let mut batch = Batch::default();
+    let mut index = 0;
+    let mut replayed_entries = 0;
+    let mut failed_entries = Vec::new();
+
+    while let Some(entry) = read_entry(&mut reader, &local_run_id)? {
+        index += 1;
+
+        if index <= state.synced_entries {
+            continue;
+        }
+
+        replayed_entries += 1;
+
+        match (entry, &mut server_run) {
+            (
+                JournalEntry::CreateRun {
+                    experiment_name,
+                    run_name,
+                    start_time,
+                    tags,
+                },
+                None,
+            ) => {
+                let run = find_or_create_run(
+                    client,
+                    &local_run_id,
+                    &experiment_name,
+                    run_name,
+                    start_time,
+                    tags,
+                )?;
+                state.server_run_id = Some(run.get_run_uuid().to_owned());
+                server_run = Some(run);
+            }
+            (JournalEntry::CreateRun { .. }, Some(_)) => {
+                return Err(Error::InvalidInput(format!(
+                    "the journal of {local_run_id} creates the run more than once"
+                )))
+            }
+            (_, None) => {
+                return Err(Error::InvalidInput(format!(
+                    "the journal of {local_run_id} does not start with the creation of the run"
+                )))
+            }
+            (entry, Some(run)) => {
+                match entry {
+                    JournalEntry::Metric(metric) => batch.metrics.push(metric),
+                    JournalEntry::Parameter(param) => {
+                        match batch.params.iter().find(|batched| batched.key == param.key) {
+                            // the server rejects batches that contain a parameter twice,
+                            // even with the same value
+                            Some(batched) if batched.value == param.value => {}
+                            // sent alone, so only this parameter fails instead of the whole batch
+                            Some(_) => {
+                                batch.send(run)?;
+                                state.synced_entries = index - 1;
+                                write_state(run_dir, &state)?;
+
+                                match run.log_parameter(&param.key, &param.value) {
+                                    Err(e) if is_rejected(&e) => failed_entries.push((index, e)),
+                                    result => result?,
+                                }
+                            }
+                            None => batch.params.push(param),
+                        }
+                    }
+                    JournalEntry::Tag(mut tag) => {
+                        if tag.key == LOGGED_MODELS_TAG {
+                            tag.value = map_logged_models_run_id(
+                                &tag.value,
+                                &local_run_id,
+                                run.get_run_uuid(),
+                            );
+                        }
+
+                        match batch.tags.iter_mut().find(|batched| batched.key == tag.key) {
+                            Some(batched) => batched.value = tag.value,
+                            None => batch.tags.push(tag),
+                        }
+                    }
+                    entry => {
+                        // the batched entries were logged before this one
+                        if batch.len() > 0 {
+                            batch.send(run)?;
+                            state.synced_entries = index - 1;
+                            write_state(run_dir, &state)?;
+                        }
+
+                        replay_entry(client, run, &local_run_id, &artifacts_dir, entry)?;
+                    }
+                }
+
+                // the batched entries are not synchronized until they were sent
+                if batch.len() > 0 && batch.len() < MAX_ENTRIES_PER_BATCH {
+                    continue;
+                }
+
+                batch.send(run)?;
+            }
+        }
+
+        state.synced_entries = index;
+        write_state(run_dir, &state)?;
+    }
+
+    if let (Some(run), true) = (&server_run, batch.len() > 0) {
+        batch.send(run)?;
+        state.synced_entries = index;
+        write_state(run_dir, &state)?;
+    }
+
+    Ok(SyncedRun {
+        local_run_id,
+        server_run_id: state.server_run_id.unwrap_or_default(),
+        replayed_entries,
+        failed_entries,
+    })
+}
+
+#[cfg(disable_experiment_tracking)]
+pub fn sync_run(_: &MlflowClient, _: &Path) -> Result<SyncedRun, Error> {
+    Ok(SyncedRun::default())
+}
+
+/// Returns the run of an earlier, interrupted synchronization or creates it.
+#[cfg(not(disable_experiment_tracking))]
+fn find_or_create_run(
+    client: &MlflowClient,
+    local_run_id: &str,
+    experiment_name: &str,
+    run_name: Option<String>,
+    start_time: u64,
+    mut tags: Vec<RunTag>,
+) -> Result<Run, Error> {
+    let experiment = Experiment::get_or_create(client, experiment_name, false)?;
+    let filter = RunFilter::new()
+        .tag(OFFLINE_RUN_ID_TAG, Comparator::Equal, local_run_id)
+        .build()?;
+
+    let existing_run = experiment
+        .search_runs(SearchRunsOptions {
+            filter: Some(filter),
+            view_type: ViewType::All,
+            max_results: Some(1),
+            ..Default::default()
+        })
+        .next()
+        .transpose()?;
+
+    if let Some(run) = existing_run {
+        return Ok(run);
+    }
+
+    tags.push(RunTag {
+        key: OFFLINE_RUN_ID_TAG.to_owned(),
+        value: local_run_id.to_owned(),
+    });
+
+    let response: CreateRunResponse = client.checked_post_request(
+        "api/2.0/mlflow/runs/create",
+        &CreateRunRequest {
+            experiment_id: experiment.get_experiment_id().to_owned(),
+            run_name,
+            start_time: start_time.into(),
+            tags,
+        },
+    )?;
+
+    let mut run = response.run;
+    run.set_client(client);
+
+    Ok(run)
+}
+
+#[cfg(not(disable_experiment_tracking))]
+fn replay_entry(
+    client: &MlflowClient,
+    run: &mut Run,
+    local_run_id: &str,
+    artifacts_dir: &Path,
+    entry: JournalEntry,
+) -> Result<(), Error> {
+    match entry {
+        JournalEntry::DeleteTag { key } => match run.delete_tag(&key) {
+            // deleted by an interrupted synchronization or never set
+            Err(e) if e.error_code() == Some(&ErrorCode::ResourceDoesNotExist) => Ok(()),
+            result => result,
+        },
+        JournalEntry::Input(input) => run.log_input(&input.dataset, input.get_context()),
+        JournalEntry::Artifact { path } => {
+            let local_path = artifacts_dir.join(&path);
+
+            // the artifact was deleted locally after it was logged
+            if !local_path.is_file() {
+                warn!("skipping the artifact {path}, it does not exist anymore");
+                return Ok(());
+            }
+
+            if local_path.file_name() == Some(MLMODEL_FILE_NAME.as_ref()) {
+                let mut descriptor = ModelDescriptor::from_yaml(&fs::read_to_string(&local_path)?)?;
+
+                // the descriptor of a model logged by the offline run refers to the local run id
+                if descriptor.run_id == local_run_id {
+                    descriptor.run_id = run.get_run_uuid().to_owned();
+                    return run.log_artifact_bytes(descriptor.to_yaml()?.into_bytes(), &path);
+                }
+            }
+
+            run.log_artifact_file(&local_path, &path)
+        }
+        JournalEntry::EndRun { status, end_time } => {
+            client.checked_post_request::<UpdateRunRequest, UpdateRunResponse>(
+                "api/2.0/mlflow/runs/update",
+                &UpdateRunRequest {
+                    run_id: run.get_run_uuid().to_owned(),
+                    status,
+                    end_time: end_time.into(),
+                },
+            )?;
+
+            Ok(())
+        }
+        JournalEntry::CreateRun { .. }
+        | JournalEntry::Metric(_)
+        | JournalEntry::Parameter(_)
+        | JournalEntry::Tag(_) => unreachable!("handled by sync_run"),
+    }
+}
+
+/// Whether the server rejected the request, so sending it again would fail as well.
+#[cfg(not(disable_experiment_tracking))]
+fn is_rejected(e: &Error) -> bool {
+    e.error_code().is_some()
+        && e.status().is_some_and(|status| {
+            status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS
+        })
+}
+
+/// Replaces the local run id in the model history of the run, see [`Run::log_model`].
+fn map_logged_models_run_id(
+    logged_models: &str,
+    local_run_id: &str,
+    server_run_id: &str,
+) -> String {
+    let Ok(mut logged_models_json) = serde_json::from_str::<Vec<Value>>(logged_models) else {
+        return logged_models.to_owned();
+    };
+
+    for model in &mut logged_models_json {
+        if model.get("run_id").and_then(Value::as_str) == Some(local_run_id) {
+            model["run_id"] = server_run_id.into();
+        }
+    }
+
+    serde_json::to_string(&logged_models_json).unwrap_or_else(|_| logged_models.to_owned())
+}
+
+/// Returns `None` at the end of the journal. An incomplete last line is left for the next synchronization.
+fn read_entry(
+    reader: &mut impl BufRead,
+    local_run_id: &str,
+) -> Result<Option<JournalEntry>, Error> {
+    let mut line = String::new();
+
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+
+    if !line.ends_with('\n') {
+        warn!("the last entry of the journal of {local_run_id} is incomplete and was not synchronized");
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+fn read_state(run_dir: &Path) -> Result<SyncState, Error> {
+    match fs::read_to_string(run_dir.join(SYNC_STATE_FILE_NAME)) {
+        Ok(state) => Ok(serde_json::from_str(&state)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(SyncState::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_state(run_dir: &Path, state: &SyncState) -> Result<(), Error> {
+    let path = run_dir.join(SYNC_STATE_FILE_NAME);
+    let temporary_path = path.with_extension("json.tmp");
+
+    // the rename replaces the old state at once, so an interruption cannot leave a broken file behind
+    fs::write(&temporary_path, serde_json::to_vec(state)?)?;
+    fs::rename(temporary_path, path)?;
+
+    Ok(())
+}
+
+#[cfg(all(test, not(disable_experiment_tracking)))]
+mod tests {
+    use std::{
+        io::Cursor,
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        thread,
+    };
+
+    use serde_json::json;
+    use tiny_http::{Header, Response, Server};
+
+    use super::*;
+
+    const SERVER_RUN_ID: &str = "server-run";
+
+    /// Tracking server that records the requests and knows a single experiment and run.
+    #[derive(Clone, Default)]
+    struct StubServer {
+        requests: Arc<Mutex<Vec<(String, String, String)>>>,
+        run_created: Arc<AtomicBool>,
+        failure: Arc<Mutex<Option<(String, u16, String)>>>,
+    }
+
+    impl StubServer {
+        fn start() -> (Self, MlflowClient) {
+            let server = Self::default();
+            let http = Server::http("127.0.0.1:0").unwrap();
+            let address = http.server_addr().to_ip().unwrap();
+            let handler = server.clone();
+
+            thread::spawn(move || {
+                for mut request in http.incoming_requests() {
+                    let mut body = Vec::new();
+                    request.as_reader().read_to_end(&mut body).unwrap();
+
+                    let (status, response) = handler.respond(
+                        request.method().as_str(),
+                        request.url(),
+                        String::from_utf8_lossy(&body).into_owned(),
+                    );
+                    let content_type = Header::from_bytes("Content-Type", "application/json");
+                    let response = Response::from_string(response.to_string())
+                        .with_status_code(status)
+                        .with_header(content_type.unwrap());
+                    let _ = request.respond(response);
+                }
+            });
+
+            (
+                server,
+                MlflowClient::new(&format!("http://{address}")).unwrap(),
+            )
+        }
+
+        /// Fails the next request whose path contains `path` with the error code.
+        fn fail_next(&self, path: &str, status: u16, error_code: &str) {
+            *self.failure.lock().unwrap() = Some((path.to_owned(), status, error_code.to_owned()));
+        }
+
+        /// Method, path and body of the requests since the last call.
+        fn take_requests(&self) -> Vec<(String, String, String)> {
+            std::mem::take(&mut *self.requests.lock().unwrap())
+        }
+
+        fn respond(&self, method: &str, path: &str, body: String) -> (u16, Value) {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((method.to_owned(), path.to_owned(), body));
+
+            let mut failure = self.failure.lock().unwrap();
+
+            if let Some((_, status, error_code)) =
+                failure.take_if(|(fail_path, _, _)| path.contains(fail_path.as_str()))
+            {
+                return (
+                    status,
+                    json!({"error_code": error_code, "message": "injected failure"}),
+                );
+            }
+
+            let run = json!({
+                "info": {
+                    "run_uuid": SERVER_RUN_ID,
+                    "experiment_id": "1",
+                    "run_name": "",
+                    "user_id": "",
+                    "status": "RUNNING",
+                    "start_time": 1,
+                    "artifact_uri": format!("mlflow-artifacts:/1/{SERVER_RUN_ID}/artifacts"),
+                    "lifecycle_stage": "active",
+                    "run_id": SERVER_RUN_ID,
+                },
+                "data": {"tags": []},
+            });
+
+            let response = if path.contains("/experiments/get-by-name") {
+                json!({"experiment": {
+                    "experiment_id": "1",
+                    "name": "test",
+                    "artifact_location": "",
+                    "lifecycle_stage": "active",
+                    "last_update_time": 1,
+                    "creation_time": 1,
+                }})
+            } else if path.contains("/runs/search") && self.run_created.load(Ordering::SeqCst) {
+                json!({"runs": [run]})
+            } else if path.contains("/runs/create") {
+                self.run_created.store(true, Ordering::SeqCst);
+                json!({ "run": run })
+            } else if path.contains("/runs/get") {
+                json!({ "run": run })
+            } else if path.contains("/runs/update") {
+                json!({"run_info": run["info"]})
+            } else {
+                json!({})
+            };
+
+            (200, response)
+        }
+    }
+
+    fn temporary_dir() -> PathBuf {
+        static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = std::env::temp_dir().join(format!(
+            "mlflow_rs-offline-{}-{}",
+            std::process::id(),
+            NEXT_DIR.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join(ARTIFACTS_DIR_NAME)).unwrap();
+
+        dir
+    }
+
+    fn metric(key: &str) -> JournalEntry {
+        JournalEntry::Metric(Metric {
+            key: key.to_owned(),
+            value: 1.0,
+            timestamp: 1,
+            step: None,
+        })
+    }
+
+    fn journal(entries: &[JournalEntry]) -> PathBuf {
+        let run_dir = temporary_dir();
+        let journal = Journal::open(&run_dir).unwrap();
+
+        journal
+            .append(&JournalEntry::CreateRun {
+                experiment_name: "test".to_owned(),
+                run_name: None,
+                start_time: 1,
+                tags: vec![],
+            })
+            .unwrap();
+
+        for entry in entries {
+            journal.append(entry).unwrap();
+        }
+
+        run_dir
+    }
+
+    fn append(run_dir: &Path, entry: &JournalEntry) {
+        Journal::open(run_dir).unwrap().append(entry).unwrap();
+    }
+
+    fn paths(requests: &[(String, String, String)]) -> Vec<&str> {
+        requests
+            .iter()
+            .map(|(_, path, _)| path.trim_start_matches("/api/2.0/"))
+            .collect()
+    }
+
+    #[test]
+    fn read_entry_ignores_an_incomplete_last_line() {
+        let mut reader = Cursor::new(
+            "{\"type\":\"delete_tag\",\"key\":\"a\"}\n{\"type\":\"artifact\",\"path\":\"b\"}\n{\"type\":\"delete_ta",
+        );
+
+        assert!(matches!(
+            read_entry(&mut reader, "run"),
+            Ok(Some(JournalEntry::DeleteTag { key })) if key == "a"
+        ));
+        assert!(matches!(
+            read_entry(&mut reader, "run"),
+            Ok(Some(JournalEntry::Artifact { path })) if path == "b"
+        ));
+        assert!(matches!(read_entry(&mut reader, "run"), Ok(None)));
+    }
+
+    #[test]
+    fn journal_entries_round_trip() {
+        let mut line = serde_json::to_vec(&JournalEntry::Metric(Metric {
+            key: "loss".to_owned(),
+            value: f32::NAN,
+            timestamp: 1,
+            step: Some(2),
+        }))
+        .unwrap();
+        line.push(b'\n');
+
+        let entry = read_entry(&mut Cursor::new(line), "run").unwrap();
+
+        assert!(matches!(
+            entry,
+            Some(JournalEntry::Metric(metric)) if metric.key == "loss" && metric.value.is_nan() && metric.step == Some(2)
+        ));
+    }
+
+    #[test]
+    fn sync_state_round_trip() {
+        let run_dir = temporary_dir();
+        let state = read_state(&run_dir).unwrap();
+
+        assert_eq!(state.server_run_id, None);
+        assert_eq!(state.synced_entries, 0);
+
+        write_state(
+            &run_dir,
+            &SyncState {
+                server_run_id: Some(SERVER_RUN_ID.to_owned()),
+                synced_entries: 3,
+            },
+        )
+        .unwrap();
+        let state = read_state(&run_dir).unwrap();
+
+        assert_eq!(state.server_run_id.as_deref(), Some(SERVER_RUN_ID));
+        assert_eq!(state.synced_entries, 3);
+        assert!(!run_dir.join("sync.json.tmp").exists());
+    }
+
+    #[test]
+    fn repeated_sync_only_sends_new_entries() {
+        let (server, client) = StubServer::start();
+        let run_dir = journal(&[
+            metric("a"),
+            metric("b"),
+            JournalEntry::Parameter(RunParameters {
+                key: "p".to_owned(),
+                value: "1".to_owned(),
+            }),
+            JournalEntry::Parameter(RunParameters {
+                key: "p".to_owned(),
+                value: "1".to_owned(),
+            }),
+            JournalEntry::Tag(RunTag {
+                key: "t".to_owned(),
+                value: "1".to_owned(),
+            }),
+            JournalEntry::Tag(RunTag {
+                key: "t".to_owned(),
+                value: "2".to_owned(),
+            }),
+        ]);
+
+        let synced_run = sync_run(&client, &run_dir).unwrap();
+        let requests = server.take_requests();
+
+        assert_eq!(synced_run.server_run_id, SERVER_RUN_ID);
+        assert_eq!(synced_run.replayed_entries, 7);
+        assert_eq!(
+            paths(&requests),
+            [
+                "mlflow/experiments/get-by-name",
+                "mlflow/runs/search",
+                "mlflow/runs/create",
+                "mlflow/runs/log-batch",
+            ]
+        );
+        assert!(requests[2].2.contains(OFFLINE_RUN_ID_TAG));
+
+        let batch: Value = serde_json::from_str(&requests[3].2).unwrap();
+
+        assert_eq!(batch["metrics"].as_array().unwrap().len(), 2);
+        assert_eq!(batch["params"], json!([{"key": "p", "value": "1"}]));
+        assert_eq!(batch["tags"], json!([{"key": "t", "value": "2"}]));
+
+        let synced_run = sync_run(&client, &run_dir).unwrap();
+
+        assert_eq!(synced_run.replayed_entries, 0);
+        assert_eq!(paths(&server.take_requests()), ["mlflow/runs/get"]);
+
+        append(
+            &run_dir,
+            &JournalEntry::EndRun {
+                status: Status::Finished,
+                end_time: 2,
+            },
+        );
+        let synced_run = sync_run(&client, &run_dir).unwrap();
+
+        assert_eq!(synced_run.replayed_entries, 1);
+        assert_eq!(
+            paths(&server.take_requests()),
+            ["mlflow/runs/get", "mlflow/runs/update"]
+        );
+    }
+
+    #[test]
+    fn interrupted_sync_resumes_after_the_last_sent_entry() {
+        let (server, client) = StubServer::start();
+        let run_dir = journal(&[
+            metric("a"),
+            JournalEntry::Artifact {
+                path: "model.bin".to_owned(),
+            },
+            metric("b"),
+            JournalEntry::EndRun {
+                status: Status::Finished,
+                end_time: 2,
+            },
+        ]);
+        fs::write(
+            run_dir.join(ARTIFACTS_DIR_NAME).join("model.bin"),
+            "weights",
+        )
+        .unwrap();
+
+        server.fail_next("model.bin", 400, "BAD_REQUEST");
+
+        assert!(sync_run(&client, &run_dir).is_err());
+        assert_eq!(
+            paths(&server.take_requests()),
+            [
+                "mlflow/experiments/get-by-name",
+                "mlflow/runs/search",
+                "mlflow/runs/create",
+                "mlflow/runs/log-batch",
+                "mlflow-artifacts/artifacts/1/server-run/artifacts/model.bin",
+            ]
+        );
+
+        let state = read_state(&run_dir).unwrap();
+
+        assert_eq!(state.server_run_id.as_deref(), Some(SERVER_RUN_ID));
+        assert_eq!(state.synced_entries, 2);
+
+        let synced_run = sync_run(&client, &run_dir).unwrap();
+
+        assert_eq!(synced_run.replayed_entries, 3);
+        assert_eq!(
+            paths(&server.take_requests()),
+            [
+                "mlflow/runs/get",
+                "mlflow-artifacts/artifacts/1/server-run/artifacts/model.bin",
+                "mlflow/runs/log-batch",
+                "mlflow/runs/update",
+            ]
+        );
+    }
+
+    #[test]
+    fn sync_finds_the_run_created_before_an_interruption() {
+        let (server, client) = StubServer::start();
+        let run_dir = journal(&[metric("a")]);
+
+        // the run is created, but the sync stops before the state is saved
+        server.fail_next("log-batch", 400, "BAD_REQUEST");
+        assert!(sync_run(&client, &run_dir).is_err());
+        fs::remove_file(run_dir.join(SYNC_STATE_FILE_NAME)).unwrap();
+        server.take_requests();
+
+        let synced_run = sync_run(&client, &run_dir).unwrap();
+
+        assert_eq!(synced_run.server_run_id, SERVER_RUN_ID);
+        assert_eq!(
+            paths(&server.take_requests()),
+            [
+                "mlflow/experiments/get-by-name",
+                "mlflow/runs/search",
+                "mlflow/runs/log-batch",
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_parameter_fails_alone() {
+        let (server, client) = StubServer::start();
+        let param = |value: &str| {
+            JournalEntry::Parameter(RunParameters {
+                key: "p".to_owned(),
+                value: value.to_owned(),
+            })
+        };
+        let run_dir = journal(&[param("1"), param("2"), metric("a")]);
+
+        server.fail_next("log-parameter", 400, "BAD_REQUEST");
+
+        let synced_run = sync_run(&client, &run_dir).unwrap();
+        let requests = server.take_requests();
+
+        assert_eq!(synced_run.replayed_entries, 4);
+        assert_eq!(synced_run.failed_entries.len(), 1);
+        assert_eq!(synced_run.failed_entries[0].0, 3);
+        assert_eq!(
+            paths(&requests),
+            [
+                "mlflow/experiments/get-by-name",
+                "mlflow/runs/search",
+                "mlflow/runs/create",
+                "mlflow/runs/log-batch",
+                "mlflow/runs/log-parameter",
+                "mlflow/runs/log-batch",
+            ]
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&requests[3].2).unwrap()["params"],
+            json!([{"key": "p", "value": "1"}])
+        );
+        assert_eq!(read_state(&run_dir).unwrap().synced_entries, 4);
+    }
+
+    #[test]
+    fn deleting_a_missing_tag_does_not_stop_the_sync() {
+        let (server, client) = StubServer::start();
+        let run_dir = journal(&[
+            JournalEntry::DeleteTag {
+                key: "t".to_owned(),
+            },
+            metric("a"),
+        ]);
+
+        server.fail_next("delete-tag", 404, "RESOURCE_DOES_NOT_EXIST");
+
+        let synced_run = sync_run(&client, &run_dir).unwrap();
+
+        assert!(synced_run.failed_entries.is_empty());
+        assert_eq!(
+            paths(&server.take_requests())[3..],
+            ["mlflow/runs/delete-tag", "mlflow/runs/log-batch"]
+        );
+    }
+
+    #[test]
+    fn logged_models_refer_to_the_server_run() {
+        let logged_models = json!([
+            {"artifact_path": "model", "run_id": "offline-1"},
+            {"artifact_path": "other", "run_id": "other-run"},
+        ])
+        .to_string();
+
+        let mapped: Value = serde_json::from_str(&map_logged_models_run_id(
+            &logged_models,
+            "offline-1",
+            SERVER_RUN_ID,
+        ))
+        .unwrap();
+
+        assert_eq!(mapped[0]["run_id"], SERVER_RUN_ID);
+        assert_eq!(mapped[1]["run_id"], "other-run");
+        assert_eq!(
+            map_logged_models_run_id("invalid", "offline-1", SERVER_RUN_ID),
+            "invalid"
+        );
+    }
+}
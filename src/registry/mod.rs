@@ -0,0 +1,4 @@
+//! Model registry of the tracking server.
+
+pub mod model_version;
+pub mod registered_model;
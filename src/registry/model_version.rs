@@ -0,0 +1,419 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::MlflowClient,
+    error::Error,
+    schemas::{
+        CreateModelVersionRequest, DeleteModelVersionRequest, DeleteModelVersionResponse,
+        DeleteModelVersionTagRequest, DeleteModelVersionTagResponse,
+        DeleteRegisteredModelAliasRequest, DeleteRegisteredModelAliasResponse,
+        GetModelVersionByAliasRequest, GetModelVersionRequest, ModelVersionResponse,
+        SetModelVersionTagRequest, SetModelVersionTagResponse, SetRegisteredModelAliasRequest,
+        SetRegisteredModelAliasResponse, TransitionModelVersionStageRequest,
+        UpdateModelVersionRequest,
+    },
+};
+
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ModelVersionTag {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    None,
+    Staging,
+    Production,
+    Archived,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ModelVersion {
+    #[serde(skip)]
+    client: MlflowClient,
+    name: String,
+    version: String,
+    #[serde(default)]
+    creation_timestamp: u64,
+    #[serde(default)]
+    last_updated_timestamp: u64,
+    #[serde(default)]
+    user_id: String,
+    #[serde(default)]
+    current_stage: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    source: String,
+    #[serde(default)]
+    run_id: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    status_message: String,
+    #[serde(default)]
+    tags: Vec<ModelVersionTag>,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+impl ModelVersion {
+    /// Registers the model files at `source`, e.g. `runs:/<run_id>/model`, as a new version of the registered model.
+    /// The version might still be in the `PENDING_REGISTRATION` status, see [`ModelVersion::wait_until_ready`].
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn create(
+        client: &MlflowClient,
+        name: &str,
+        source: &str,
+        run_id: Option<&str>,
+        tags: Vec<ModelVersionTag>,
+    ) -> Result<Self, Error> {
+        let response: ModelVersionResponse = client.checked_post_request(
+            "api/2.0/mlflow/model-versions/create",
+            &CreateModelVersionRequest {
+                name: name.to_owned(),
+                source: source.to_owned(),
+                run_id: run_id.map(|x| x.to_owned()),
+                tags,
+                description: None,
+            },
+        )?;
+
+        Ok(response.into_version(client))
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn create(
+        _: &MlflowClient,
+        _: &str,
+        _: &str,
+        _: Option<&str>,
+        _: Vec<ModelVersionTag>,
+    ) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn get(client: &MlflowClient, name: &str, version: &str) -> Result<Self, Error> {
+        let response: ModelVersionResponse = client.checked_get_request(
+            "api/2.0/mlflow/model-versions/get",
+            &GetModelVersionRequest {
+                name: name.to_owned(),
+                version: version.to_owned(),
+            },
+        )?;
+
+        Ok(response.into_version(client))
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn get(_: &MlflowClient, _: &str, _: &str) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn get_by_alias(client: &MlflowClient, name: &str, alias: &str) -> Result<Self, Error> {
+        let response: ModelVersionResponse = client.checked_get_request(
+            "api/2.0/mlflow/registered-models/alias",
+            &GetModelVersionByAliasRequest {
+                name: name.to_owned(),
+                alias: alias.to_owned(),
+            },
+        )?;
+
+        Ok(response.into_version(client))
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn get_by_alias(_: &MlflowClient, _: &str, _: &str) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    /// Polls the server until the version is ready and fails if the registration failed or took longer than `timeout`.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn wait_until_ready(&mut self, timeout: Duration) -> Result<(), Error> {
+        let start = Instant::now();
+
+        loop {
+            match self.status.as_str() {
+                "PENDING_REGISTRATION" => {}
+                "FAILED_REGISTRATION" => {
+                    return Err(self.registration_error(self.status_message.clone()))
+                }
+                _ => return Ok(()),
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(self.registration_error(format!(
+                    "the version is still pending after {timeout:?}"
+                )));
+            }
+
+            thread::sleep(READY_POLL_INTERVAL);
+            self.refresh()?;
+        }
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn wait_until_ready(&mut self, _: Duration) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn update_description(&mut self, description: &str) -> Result<(), Error> {
+        let response: ModelVersionResponse = self.client.checked_patch_request(
+            "api/2.0/mlflow/model-versions/update",
+            &UpdateModelVersionRequest {
+                name: self.name.clone(),
+                version: self.version.clone(),
+                description: description.to_owned(),
+            },
+        )?;
+
+        *self = response.into_version(&self.client);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn update_description(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Moves the version into the stage. Other versions in the stage are archived if `archive_existing_versions` is true.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn transition_stage(
+        &mut self,
+        stage: Stage,
+        archive_existing_versions: bool,
+    ) -> Result<(), Error> {
+        let response: ModelVersionResponse = self.client.checked_post_request(
+            "api/2.0/mlflow/model-versions/transition-stage",
+            &TransitionModelVersionStageRequest {
+                name: self.name.clone(),
+                version: self.version.clone(),
+                stage,
+                archive_existing_versions,
+            },
+        )?;
+
+        *self = response.into_version(&self.client);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn transition_stage(&mut self, _: Stage, _: bool) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete(self) -> Result<(), Error> {
+        self.client
+            .checked_delete_request::<DeleteModelVersionRequest, DeleteModelVersionResponse>(
+                "api/2.0/mlflow/model-versions/delete",
+                &DeleteModelVersionRequest {
+                    name: self.name,
+                    version: self.version,
+                },
+            )?;
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Adds the tag or overwrites its value if it exists already.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn set_tag(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<SetModelVersionTagRequest, SetModelVersionTagResponse>(
+                "api/2.0/mlflow/model-versions/set-tag",
+                &SetModelVersionTagRequest {
+                    name: self.name.clone(),
+                    version: self.version.clone(),
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                },
+            )?;
+
+        match self.tags.iter_mut().find(|tag| tag.key == key) {
+            Some(tag) => tag.value = value.to_owned(),
+            None => self.tags.push(ModelVersionTag {
+                key: key.to_owned(),
+                value: value.to_owned(),
+            }),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn set_tag(&mut self, _: &str, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete_tag(&mut self, key: &str) -> Result<(), Error> {
+        self.client
+            .checked_delete_request::<DeleteModelVersionTagRequest, DeleteModelVersionTagResponse>(
+                "api/2.0/mlflow/model-versions/delete-tag",
+                &DeleteModelVersionTagRequest {
+                    name: self.name.clone(),
+                    version: self.version.clone(),
+                    key: key.to_owned(),
+                },
+            )?;
+
+        self.tags.retain(|tag| tag.key != key);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete_tag(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Points the alias of the registered model to this version.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn set_alias(&mut self, alias: &str) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<SetRegisteredModelAliasRequest, SetRegisteredModelAliasResponse>(
+                "api/2.0/mlflow/registered-models/alias",
+                &SetRegisteredModelAliasRequest {
+                    name: self.name.clone(),
+                    alias: alias.to_owned(),
+                    version: self.version.clone(),
+                },
+            )?;
+
+        if !self.aliases.iter().any(|cached| cached == alias) {
+            self.aliases.push(alias.to_owned());
+        }
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn set_alias(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete_alias(&mut self, alias: &str) -> Result<(), Error> {
+        self.client
+            .checked_delete_request::<DeleteRegisteredModelAliasRequest, DeleteRegisteredModelAliasResponse>(
+                "api/2.0/mlflow/registered-models/alias",
+                &DeleteRegisteredModelAliasRequest {
+                    name: self.name.clone(),
+                    alias: alias.to_owned(),
+                },
+            )?;
+
+        self.aliases.retain(|cached| cached != alias);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete_alias(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    fn refresh(&mut self) -> Result<(), Error> {
+        *self = Self::get(&self.client, &self.name, &self.version)?;
+
+        Ok(())
+    }
+
+    fn registration_error(&self, message: String) -> Error {
+        Error::ModelRegistration {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            message,
+        }
+    }
+
+    pub fn get_client(&self) -> &MlflowClient {
+        &self.client
+    }
+
+    pub(crate) fn set_client(&mut self, client: &MlflowClient) {
+        self.client = client.clone();
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn get_creation_timestamp(&self) -> u64 {
+        self.creation_timestamp
+    }
+
+    pub fn get_last_updated_timestamp(&self) -> u64 {
+        self.last_updated_timestamp
+    }
+
+    pub fn get_user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    pub fn get_current_stage(&self) -> &str {
+        &self.current_stage
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn get_run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// One of `PENDING_REGISTRATION`, `FAILED_REGISTRATION` or `READY`.
+    pub fn get_status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn get_status_message(&self) -> &str {
+        &self.status_message
+    }
+
+    pub fn get_tags(&self) -> &Vec<ModelVersionTag> {
+        &self.tags
+    }
+
+    pub fn get_aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+}
+
+impl ModelVersionResponse {
+    fn into_version(self, client: &MlflowClient) -> ModelVersion {
+        let mut version = self.model_version;
+        version.set_client(client);
+
+        version
+    }
+}
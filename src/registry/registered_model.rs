@@ -0,0 +1,357 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::MlflowClient,
+    error::Error,
+    registry::model_version::ModelVersion,
+    schemas::{
+        CreateRegisteredModelRequest, DeleteRegisteredModelAliasRequest,
+        DeleteRegisteredModelAliasResponse, DeleteRegisteredModelRequest,
+        DeleteRegisteredModelResponse, DeleteRegisteredModelTagRequest,
+        DeleteRegisteredModelTagResponse, GetRegisteredModelRequest, RegisteredModelResponse,
+        RenameRegisteredModelRequest, SearchRegisteredModelsRequest,
+        SearchRegisteredModelsResponse, SetRegisteredModelAliasRequest,
+        SetRegisteredModelAliasResponse, SetRegisteredModelTagRequest,
+        SetRegisteredModelTagResponse, UpdateRegisteredModelRequest,
+    },
+    utils::Paginated,
+};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RegisteredModelTag {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct RegisteredModelAlias {
+    pub alias: String,
+    pub version: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SearchRegisteredModelsOptions {
+    /// e.g. `name LIKE 'classifier-%'` or `tags.team = 'research'`
+    pub filter: Option<String>,
+    /// e.g. `name ASC` or `last_updated_timestamp DESC`
+    pub order_by: Vec<String>,
+    /// Number of models that are requested at once. The server default is used if not set.
+    pub page_size: Option<u32>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct RegisteredModel {
+    #[serde(skip)]
+    client: MlflowClient,
+    name: String,
+    #[serde(default)]
+    creation_timestamp: u64,
+    #[serde(default)]
+    last_updated_timestamp: u64,
+    #[serde(default)]
+    user_id: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    tags: Vec<RegisteredModelTag>,
+    #[serde(default)]
+    aliases: Vec<RegisteredModelAlias>,
+    #[serde(default)]
+    latest_versions: Vec<ModelVersion>,
+}
+
+impl RegisteredModel {
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn create(
+        client: &MlflowClient,
+        name: &str,
+        description: Option<&str>,
+        tags: Vec<RegisteredModelTag>,
+    ) -> Result<Self, Error> {
+        let response: RegisteredModelResponse = client.checked_post_request(
+            "api/2.0/mlflow/registered-models/create",
+            &CreateRegisteredModelRequest {
+                name: name.to_owned(),
+                tags,
+                description: description.map(|x| x.to_owned()),
+            },
+        )?;
+
+        Ok(response.into_model(client))
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn create(
+        _: &MlflowClient,
+        _: &str,
+        _: Option<&str>,
+        _: Vec<RegisteredModelTag>,
+    ) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn get(client: &MlflowClient, name: &str) -> Result<Self, Error> {
+        let response: RegisteredModelResponse = client.checked_get_request(
+            "api/2.0/mlflow/registered-models/get",
+            &GetRegisteredModelRequest {
+                name: name.to_owned(),
+            },
+        )?;
+
+        Ok(response.into_model(client))
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn get(_: &MlflowClient, _: &str) -> Result<Self, Error> {
+        Ok(Self::default())
+    }
+
+    /// Returns all registered models matching the options. Further pages are requested while iterating.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn search(
+        client: &MlflowClient,
+        options: SearchRegisteredModelsOptions,
+    ) -> impl Iterator<Item = Result<Self, Error>> {
+        let client = client.clone();
+
+        Paginated::new(move |page_token| {
+            let response: SearchRegisteredModelsResponse = client.checked_get_request(
+                "api/2.0/mlflow/registered-models/search",
+                &SearchRegisteredModelsRequest {
+                    filter: options.filter.clone(),
+                    max_results: options.page_size,
+                    order_by: options.order_by.clone(),
+                    page_token,
+                },
+            )?;
+
+            let mut models = response.registered_models;
+
+            for model in &mut models {
+                model.set_client(&client);
+            }
+
+            Ok((models, response.next_page_token))
+        })
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn search(
+        _: &MlflowClient,
+        _: SearchRegisteredModelsOptions,
+    ) -> impl Iterator<Item = Result<Self, Error>> {
+        std::iter::empty()
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn rename(&mut self, new_name: &str) -> Result<(), Error> {
+        let response: RegisteredModelResponse = self.client.checked_post_request(
+            "api/2.0/mlflow/registered-models/rename",
+            &RenameRegisteredModelRequest {
+                name: self.name.clone(),
+                new_name: new_name.to_owned(),
+            },
+        )?;
+
+        *self = response.into_model(&self.client);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn rename(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn update_description(&mut self, description: &str) -> Result<(), Error> {
+        let response: RegisteredModelResponse = self.client.checked_patch_request(
+            "api/2.0/mlflow/registered-models/update",
+            &UpdateRegisteredModelRequest {
+                name: self.name.clone(),
+                description: description.to_owned(),
+            },
+        )?;
+
+        *self = response.into_model(&self.client);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn update_description(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Deletes the model together with all of its versions. This cannot be undone.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete(self) -> Result<(), Error> {
+        self.client
+            .checked_delete_request::<DeleteRegisteredModelRequest, DeleteRegisteredModelResponse>(
+                "api/2.0/mlflow/registered-models/delete",
+                &DeleteRegisteredModelRequest { name: self.name },
+            )?;
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Adds the tag or overwrites its value if it exists already.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn set_tag(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<SetRegisteredModelTagRequest, SetRegisteredModelTagResponse>(
+                "api/2.0/mlflow/registered-models/set-tag",
+                &SetRegisteredModelTagRequest {
+                    name: self.name.clone(),
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                },
+            )?;
+
+        match self.tags.iter_mut().find(|tag| tag.key == key) {
+            Some(tag) => tag.value = value.to_owned(),
+            None => self.tags.push(RegisteredModelTag {
+                key: key.to_owned(),
+                value: value.to_owned(),
+            }),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn set_tag(&mut self, _: &str, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete_tag(&mut self, key: &str) -> Result<(), Error> {
+        self.client
+            .checked_delete_request::<DeleteRegisteredModelTagRequest, DeleteRegisteredModelTagResponse>(
+                "api/2.0/mlflow/registered-models/delete-tag",
+                &DeleteRegisteredModelTagRequest {
+                    name: self.name.clone(),
+                    key: key.to_owned(),
+                },
+            )?;
+
+        self.tags.retain(|tag| tag.key != key);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete_tag(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Points the alias to the version. An alias that points to another version already is moved.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn set_alias(&mut self, alias: &str, version: &str) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<SetRegisteredModelAliasRequest, SetRegisteredModelAliasResponse>(
+                "api/2.0/mlflow/registered-models/alias",
+                &SetRegisteredModelAliasRequest {
+                    name: self.name.clone(),
+                    alias: alias.to_owned(),
+                    version: version.to_owned(),
+                },
+            )?;
+
+        match self.aliases.iter_mut().find(|cached| cached.alias == alias) {
+            Some(cached) => cached.version = version.to_owned(),
+            None => self.aliases.push(RegisteredModelAlias {
+                alias: alias.to_owned(),
+                version: version.to_owned(),
+            }),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn set_alias(&mut self, _: &str, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete_alias(&mut self, alias: &str) -> Result<(), Error> {
+        self.client
+            .checked_delete_request::<DeleteRegisteredModelAliasRequest, DeleteRegisteredModelAliasResponse>(
+                "api/2.0/mlflow/registered-models/alias",
+                &DeleteRegisteredModelAliasRequest {
+                    name: self.name.clone(),
+                    alias: alias.to_owned(),
+                },
+            )?;
+
+        self.aliases.retain(|cached| cached.alias != alias);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete_alias(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub fn get_client(&self) -> &MlflowClient {
+        &self.client
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_creation_timestamp(&self) -> u64 {
+        self.creation_timestamp
+    }
+
+    pub fn get_last_updated_timestamp(&self) -> u64 {
+        self.last_updated_timestamp
+    }
+
+    pub fn get_user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn get_tags(&self) -> &Vec<RegisteredModelTag> {
+        &self.tags
+    }
+
+    pub fn get_aliases(&self) -> &Vec<RegisteredModelAlias> {
+        &self.aliases
+    }
+
+    /// The latest version of each stage.
+    pub fn get_latest_versions(&self) -> &Vec<ModelVersion> {
+        &self.latest_versions
+    }
+
+    fn set_client(&mut self, client: &MlflowClient) {
+        self.client = client.clone();
+
+        for version in &mut self.latest_versions {
+            version.set_client(client);
+        }
+    }
+}
+
+impl RegisteredModelResponse {
+    fn into_model(self, client: &MlflowClient) -> RegisteredModel {
+        let mut model = self.registered_model;
+        model.set_client(client);
+
+        model
+    }
+}
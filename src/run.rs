@@ -1,71 +1,178 @@
 use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
     panic::{self, RefUnwindSafe, UnwindSafe},
-    path::Path,
+    path::{Path, PathBuf},
     process::exit,
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
     },
-    time::SystemTime,
+    thread,
+    time::{Duration, SystemTime},
 };
 
 use log::Log;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
-use thiserror::Error;
 
 use crate::{
+    artifacts::{
+        artifact_repository, collect_files, copy_with_progress, multipart_upload,
+        proxied_artifact_path, ArtifactBackend, ArtifactRepository, DownloadProgress,
+        LocalArtifactRepository, MultipartUploadConfig, UploadDirOptions, UploadSummary,
+    },
+    async_logging::{AsyncLoggingConfig, LogQueue, QueueEntry},
+    client::MlflowClient,
+    dataset::{Dataset, DatasetInput, InputTag, CONTEXT_TAG},
+    error::{Error, ErrorCode},
+    experiment::ViewType,
     logger::ExperimentLogger,
+    model::{ModelDescriptor, ModelSignature, MLMODEL_FILE_NAME},
+    offline::{Journal, JournalEntry},
+    registry::{model_version::ModelVersion, registered_model::RegisteredModel},
     schemas::{
-        GetRunRequest, GetRunResponse, LogMetricRequest, LogMetricResponse, LogParameterRequest,
-        LogParameterResponse, UpdateRunRequest, UpdateRunResponse, ListArtifactsRequest, ListArtifactsResponse
+        DeleteRunRequest, DeleteRunResponse, DeleteTagRequest, DeleteTagResponse,
+        GetMetricHistoryRequest, GetMetricHistoryResponse, GetRunRequest, GetRunResponse,
+        LogBatchRequest, LogBatchResponse, LogInputsRequest, LogInputsResponse, LogMetricRequest,
+        LogMetricResponse, LogParameterRequest, LogParameterResponse, RestoreRunRequest,
+        RestoreRunResponse, SearchRunsRequest, SearchRunsResponse, SetTagRequest, SetTagResponse,
+        UpdateRunRequest, UpdateRunResponse,
     },
-    utils::{checked_get_request, checked_post_request},
+    utils::Paginated,
 };
 
+#[derive(Clone, Debug, Default)]
+pub struct SearchRunsOptions {
+    /// e.g. `metrics.loss < 0.1 AND params.lr = '0.01'`, see [`crate::filter::RunFilter`]
+    pub filter: Option<String>,
+    /// e.g. `metrics.loss ASC` or `attributes.start_time DESC`
+    pub order_by: Vec<String>,
+    pub view_type: ViewType,
+    /// Maximum number of runs that are returned in total.
+    pub max_results: Option<usize>,
+    /// Number of runs that are requested at once, at most 50000. Defaults to `max_results` or the server default.
+    pub page_size: Option<u32>,
+}
+
 #[derive(Deserialize, Default)]
 pub struct Run {
     #[serde(skip)]
-    api_root: String,
+    client: MlflowClient,
+    #[serde(skip)]
+    log_queue: Option<LogQueue>,
+    #[serde(skip)]
+    journal: Option<Journal>,
     info: RunInfo,
     data: RunData,
+    #[serde(default)]
+    inputs: RunInputs,
 }
 
 #[derive(Deserialize, Default)]
 pub(crate) struct RunInfo {
-    run_uuid: String,
-    experiment_id: String,
-    run_name: String,
-    user_id: String,
-    status: String,
-    start_time: u64,
-    artifact_uri: String,
-    lifecycle_stage: String,
-    run_id: String,
+    pub(crate) run_uuid: String,
+    pub(crate) experiment_id: String,
+    pub(crate) run_name: String,
+    pub(crate) user_id: String,
+    pub(crate) status: String,
+    pub(crate) start_time: u64,
+    pub(crate) artifact_uri: String,
+    pub(crate) lifecycle_stage: String,
+    pub(crate) run_id: String,
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct RunData {
+    pub(crate) tags: Vec<RunTag>,
+    #[serde(default)]
+    pub(crate) params: Vec<RunParameters>,
+    #[serde(default)]
+    pub(crate) metrics: Vec<Metric>,
 }
 
 #[derive(Deserialize, Default)]
-struct RunData {
-    tags: Vec<RunTag>,
+pub(crate) struct RunInputs {
     #[serde(default)]
-    params: Vec<RunParameters>
+    pub(crate) dataset_inputs: Vec<DatasetInput>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct RunTag {
     pub key: String,
     pub value: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct RunParameters {
     pub key: String,
     pub value: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Metric {
+    pub key: String,
+    #[serde(
+        serialize_with = "serialize_metric_value",
+        deserialize_with = "deserialize_metric_value"
+    )]
+    pub value: f32,
+    pub timestamp: u64,
+    #[serde(default)]
+    pub step: Option<u64>,
+}
+
+// JSON has no NaN and infinite values, the server sends them as strings
+fn serialize_metric_value<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+    if value.is_nan() {
+        serializer.serialize_str("NaN")
+    } else if value.is_infinite() {
+        serializer.serialize_str(if *value > 0.0 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        })
+    } else {
+        serializer.serialize_f32(*value)
+    }
+}
+
+fn deserialize_metric_value<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Number(f32),
+        String(String),
+    }
+
+    match Value::deserialize(deserializer)? {
+        Value::Number(value) => Ok(value),
+        Value::String(value) => match value.as_str() {
+            "NaN" => Ok(f32::NAN),
+            "Infinity" => Ok(f32::INFINITY),
+            "-Infinity" => Ok(f32::NEG_INFINITY),
+            _ => Err(D::Error::custom(format!("invalid metric value {value}"))),
+        },
+    }
+}
+
+/// Rejects names that would escape the download directory.
+fn local_name<'a>(artifact_path: &str, name: &'a str) -> Result<&'a str, Error> {
+    if name.is_empty() || name == "." || name == ".." || name.contains('\\') {
+        return Err(Error::InvalidInput(format!(
+            "the artifact path {artifact_path} cannot be stored locally"
+        )));
+    }
+
+    Ok(name)
+}
+
 #[derive(Deserialize)]
 pub struct ArtifactInfo {
     pub path: String,
+    // the server leaves out false values
+    #[serde(default)]
     pub is_dir: bool
 }
 
@@ -77,167 +184,675 @@ pub enum Status {
     Failed,
 }
 
-#[derive(Error, Debug)]
-#[error("value is not a map")]
-struct NotAMapError;
+impl Status {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Finished => "FINISHED",
+            Self::Killed => "KILLED",
+            Self::Failed => "FAILED",
+        }
+    }
+}
+
+// limits of the log-batch endpoint of the MLflow server
+const MAX_METRICS_PER_BATCH: usize = 1000;
+const MAX_PARAMS_PER_BATCH: usize = 100;
+const MAX_TAGS_PER_BATCH: usize = 100;
+pub(crate) const MAX_ENTITIES_PER_BATCH: usize = 1000;
 
-#[derive(Error, Debug)]
-#[error("Cannot download artifacts when experiment tracking is disabled.")]
-#[cfg(disable_experiment_tracking)]
-struct ArtifactDownloadError;
+// limit of the runs/search endpoint of the MLflow server
+const MAX_RUNS_PER_PAGE: usize = 50_000;
+
+// same default as the python client
+const MODEL_REGISTRATION_TIMEOUT: Duration = Duration::from_secs(300);
+
+// the UI lists the models of a run based on this tag
+pub(crate) const LOGGED_MODELS_TAG: &str = "mlflow.log-model.history";
 
 impl Run {
     #[cfg(not(disable_experiment_tracking))]
-    pub fn get_run(api_root: &str, run_id: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let mut run = checked_get_request::<GetRunRequest, GetRunResponse>(
-            &format!("{}/api/2.0/mlflow/runs/get", api_root),
-            &GetRunRequest {
-                run_id: run_id.to_owned(),
-            },
-        )?
-        .run;
-
-        run.set_api_root(api_root);
+    pub fn get_run(client: &MlflowClient, run_id: &str) -> Result<Self, Error> {
+        let mut run = client
+            .checked_get_request::<GetRunRequest, GetRunResponse>(
+                "api/2.0/mlflow/runs/get",
+                &GetRunRequest {
+                    run_id: run_id.to_owned(),
+                },
+            )?
+            .run;
+
+        run.set_client(client);
 
         Ok(run)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn get_run(api_root: &str, run_id: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn get_run(client: &MlflowClient, run_id: &str) -> Result<Self, Error> {
         Ok(Run::default())
     }
 
+    /// Returns all runs of the experiments matching the options. Further pages are requested while iterating.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn search(
+        client: &MlflowClient,
+        experiment_ids: &[&str],
+        options: SearchRunsOptions,
+    ) -> impl Iterator<Item = Result<Self, Error>> {
+        let client = client.clone();
+        let experiment_ids: Vec<String> = experiment_ids.iter().map(|&id| id.to_owned()).collect();
+        let max_results = options.max_results.unwrap_or(usize::MAX);
+        // don't request more runs than needed if only a few are wanted, the rest is limited by take
+        let page_size = options
+            .page_size
+            .map(|page_size| page_size as usize)
+            .or(options.max_results)
+            .map(|page_size| page_size.min(MAX_RUNS_PER_PAGE) as u32);
+
+        Paginated::new(move |page_token| {
+            let response: SearchRunsResponse = client.checked_post_request(
+                "api/2.0/mlflow/runs/search",
+                &SearchRunsRequest {
+                    experiment_ids: experiment_ids.clone(),
+                    filter: options.filter.clone(),
+                    run_view_type: options.view_type,
+                    max_results: page_size,
+                    order_by: options.order_by.clone(),
+                    page_token,
+                },
+            )?;
+
+            let mut runs = response.runs;
+
+            for run in &mut runs {
+                run.set_client(&client);
+            }
+
+            Ok((runs, response.next_page_token))
+        })
+        .take(max_results)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn search(
+        _: &MlflowClient,
+        _: &[&str],
+        _: SearchRunsOptions,
+    ) -> impl Iterator<Item = Result<Self, Error>> {
+        std::iter::empty()
+    }
+
+    /// Deletes all runs of the experiments matching the options and returns them.
+    /// With `dry_run` nothing is deleted and only the runs that would be deleted are returned.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete_matching(
+        client: &MlflowClient,
+        experiment_ids: &[&str],
+        options: SearchRunsOptions,
+        dry_run: bool,
+    ) -> Result<Vec<Self>, Error> {
+        // all pages are requested first, deleting while paginating would shift the pages
+        let mut runs =
+            Self::search(client, experiment_ids, options).collect::<Result<Vec<_>, _>>()?;
+
+        if !dry_run {
+            for run in &mut runs {
+                run.delete()?;
+            }
+        }
+
+        Ok(runs)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete_matching(
+        _: &MlflowClient,
+        _: &[&str],
+        _: SearchRunsOptions,
+        _: bool,
+    ) -> Result<Vec<Self>, Error> {
+        Ok(vec![])
+    }
+
+    /// Moves the run into the deleted state. It can be restored until it is garbage collected.
+    /// An error of the asynchronous logging is returned after the run was deleted anyway.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete(&mut self) -> Result<(), Error> {
+        let flushed = self.flush();
+
+        self.client
+            .checked_post_request::<DeleteRunRequest, DeleteRunResponse>(
+                "api/2.0/mlflow/runs/delete",
+                &DeleteRunRequest {
+                    run_id: self.info.run_id.clone(),
+                },
+            )?;
+
+        self.refresh_info()?;
+
+        flushed
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn restore(&mut self) -> Result<(), Error> {
+        self.client
+            .checked_post_request::<RestoreRunRequest, RestoreRunResponse>(
+                "api/2.0/mlflow/runs/restore",
+                &RestoreRunRequest {
+                    run_id: self.info.run_id.clone(),
+                },
+            )?;
+
+        self.refresh_info()
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn restore(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    fn refresh_info(&mut self) -> Result<(), Error> {
+        self.info = Self::get_run(&self.client, &self.info.run_id)?.info;
+
+        Ok(())
+    }
+
+    /// An error of the asynchronous logging is returned after the run was ended anyway.
     #[cfg(not(disable_experiment_tracking))]
-    pub fn end_run(&mut self, status: Status) -> Result<(), Box<dyn std::error::Error>> {
-        let new_run_info = checked_post_request::<UpdateRunRequest, UpdateRunResponse>(
-            &format!("{}/api/2.0/mlflow/runs/update", self.api_root),
-            &UpdateRunRequest {
-                run_id: self.info.run_id.clone(),
+    pub fn end_run(&mut self, status: Status) -> Result<(), Error> {
+        if let Some(journal) = &self.journal {
+            self.info.status = status.as_str().to_owned();
+            journal.append(&JournalEntry::EndRun {
                 status,
                 end_time: SystemTime::now()
                     .duration_since(SystemTime::UNIX_EPOCH)?
-                    .as_millis(),
-            },
-        )?
-        .run_info;
+                    .as_millis() as u64,
+            })?;
+
+            return Ok(());
+        }
+
+        let flushed = self.flush();
+
+        let new_run_info = self
+            .client
+            .checked_post_request::<UpdateRunRequest, UpdateRunResponse>(
+                "api/2.0/mlflow/runs/update",
+                &UpdateRunRequest {
+                    run_id: self.info.run_id.clone(),
+                    status,
+                    end_time: SystemTime::now()
+                        .duration_since(SystemTime::UNIX_EPOCH)?
+                        .as_millis(),
+                },
+            )?
+            .run_info;
 
         self.info = new_run_info;
 
+        flushed
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn end_run(&mut self, _: Status) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Metrics, parameters and tags will be sent by a background thread in batches instead of blocking the caller.
+    /// Errors of the background thread are returned by `flush` and `end_run`.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn enable_async_logging(&mut self, config: AsyncLoggingConfig) {
+        self.log_queue = Some(LogQueue::new(&self.client, &self.info.run_id, config));
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn enable_async_logging(&mut self, _: AsyncLoggingConfig) {}
+
+    /// Waits until all asynchronously logged entries were sent to the server.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn flush(&self) -> Result<(), Error> {
+        if let Some(log_queue) = &self.log_queue {
+            log_queue.flush()?;
+        }
+
         Ok(())
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn end_run(&mut self, _: Status) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn flush(&self) -> Result<(), Error> {
         Ok(())
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn log_metric(
-        &self,
-        key: &str,
-        value: f32,
-        step: Option<u64>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        checked_post_request::<LogMetricRequest, LogMetricResponse>(
-            &format!("{}/api/2.0/mlflow/runs/log-metric", self.api_root),
-            &LogMetricRequest {
-                run_id: self.info.run_id.clone(),
+    pub fn log_metric(&self, key: &str, value: f32, step: Option<u64>) -> Result<(), Error> {
+        if self.journal.is_some() || self.log_queue.is_some() {
+            let metric = Metric {
                 key: key.to_owned(),
                 value,
                 timestamp: SystemTime::now()
                     .duration_since(SystemTime::UNIX_EPOCH)?
-                    .as_millis(),
+                    .as_millis() as u64,
                 step,
-            },
-        )?;
+            };
+
+            return self.log_batch(&[metric], &[], &[]);
+        }
+
+        self.client
+            .checked_post_request::<LogMetricRequest, LogMetricResponse>(
+                "api/2.0/mlflow/runs/log-metric",
+                &LogMetricRequest {
+                    run_id: self.info.run_id.clone(),
+                    key: key.to_owned(),
+                    value,
+                    timestamp: SystemTime::now()
+                        .duration_since(SystemTime::UNIX_EPOCH)?
+                        .as_millis(),
+                    step,
+                },
+            )?;
 
         Ok(())
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn log_metric(
-        &self,
-        _: &str,
-        _: f32,
-        _: Option<u64>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn log_metric(&self, _: &str, _: f32, _: Option<u64>) -> Result<(), Error> {
         Ok(())
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn log_parameter(&self, key: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
-        checked_post_request::<LogParameterRequest, LogParameterResponse>(
-            &format!("{}/api/2.0/mlflow/runs/log-parameter", self.api_root),
-            &LogParameterRequest {
-                run_id: self.info.run_id.clone(),
+    pub fn log_parameter(&self, key: &str, value: &str) -> Result<(), Error> {
+        if self.journal.is_some() || self.log_queue.is_some() {
+            let param = RunParameters {
                 key: key.to_owned(),
                 value: value.to_owned(),
-            },
-        )?;
+            };
+
+            return self.log_batch(&[], &[param], &[]);
+        }
+
+        self.client
+            .checked_post_request::<LogParameterRequest, LogParameterResponse>(
+                "api/2.0/mlflow/runs/log-parameter",
+                &LogParameterRequest {
+                    run_id: self.info.run_id.clone(),
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                },
+            )?;
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn log_parameter(&self, _: &str, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Records that the run used the dataset. The context describes its purpose, e.g. `training` or `evaluation`.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn log_input(&mut self, dataset: &Dataset, context: Option<&str>) -> Result<(), Error> {
+        let input = DatasetInput {
+            tags: context
+                .map(|context| InputTag {
+                    key: CONTEXT_TAG.to_owned(),
+                    value: context.to_owned(),
+                })
+                .into_iter()
+                .collect(),
+            dataset: dataset.clone(),
+        };
+
+        if let Some(journal) = &self.journal {
+            journal.append(&JournalEntry::Input(input.clone()))?;
+        } else {
+            self.client
+                .checked_post_request::<LogInputsRequest, LogInputsResponse>(
+                    "api/2.0/mlflow/runs/log-inputs",
+                    &LogInputsRequest {
+                        run_id: self.info.run_id.clone(),
+                        datasets: std::slice::from_ref(&input),
+                    },
+                )?;
+        }
+
+        self.inputs.dataset_inputs.push(input);
 
         Ok(())
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn log_parameter(&self, _: &str, _: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn log_input(&mut self, _: &Dataset, _: Option<&str>) -> Result<(), Error> {
         Ok(())
     }
 
+    /// Adds the tag or overwrites its value if it exists already.
     #[cfg(not(disable_experiment_tracking))]
-    pub fn log_parameter_struct_as_json<T: Serialize>(
+    pub fn set_tag(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let tag = RunTag {
+            key: key.to_owned(),
+            value: value.to_owned(),
+        };
+
+        if let Some(journal) = &self.journal {
+            journal.append(&JournalEntry::Tag(tag.clone()))?;
+        } else if let Some(log_queue) = &self.log_queue {
+            log_queue.push(QueueEntry::Tag(tag.clone()));
+        } else {
+            self.client
+                .checked_post_request::<SetTagRequest, SetTagResponse>(
+                    "api/2.0/mlflow/runs/set-tag",
+                    &SetTagRequest {
+                        run_id: self.info.run_id.clone(),
+                        key: tag.key.clone(),
+                        value: tag.value.clone(),
+                    },
+                )?;
+        }
+
+        self.update_cached_tag(tag);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn set_tag(&mut self, _: &str, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Sets all tags with as few requests as possible.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn set_tags(&mut self, tags: &[RunTag]) -> Result<(), Error> {
+        self.log_batch(&[], &[], tags)?;
+
+        for tag in tags {
+            self.update_cached_tag(tag.clone());
+        }
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn set_tags(&mut self, _: &[RunTag]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn delete_tag(&mut self, key: &str) -> Result<(), Error> {
+        if let Some(journal) = &self.journal {
+            journal.append(&JournalEntry::DeleteTag {
+                key: key.to_owned(),
+            })?;
+        } else {
+            // a queued set_tag of the same key must not be sent after the deletion
+            self.flush()?;
+
+            self.client
+                .checked_post_request::<DeleteTagRequest, DeleteTagResponse>(
+                    "api/2.0/mlflow/runs/delete-tag",
+                    &DeleteTagRequest {
+                        run_id: self.info.run_id.clone(),
+                        key: key.to_owned(),
+                    },
+                )?;
+        }
+
+        self.data.tags.retain(|tag| tag.key != key);
+
+        Ok(())
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete_tag(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn update_cached_tag(&mut self, tag: RunTag) {
+        match self
+            .data
+            .tags
+            .iter_mut()
+            .find(|cached| cached.key == tag.key)
+        {
+            Some(cached) => cached.value = tag.value,
+            None => self.data.tags.push(tag),
+        }
+    }
+
+    /// Logs metrics, parameters and tags with as few requests as possible.
+    /// The data is split into multiple requests if it exceeds the limits of the server.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn log_batch(
         &self,
-        parameters: T,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let parsed = serde_json::to_value(parameters)?;
-        self.log_serde_value_as_parameters("", parsed)?;
+        metrics: &[Metric],
+        params: &[RunParameters],
+        tags: &[RunTag],
+    ) -> Result<(), Error> {
+        if let Some(journal) = &self.journal {
+            let entries = metrics
+                .iter()
+                .map(|metric| JournalEntry::Metric(metric.clone()))
+                .chain(
+                    params
+                        .iter()
+                        .map(|param| JournalEntry::Parameter(param.clone())),
+                )
+                .chain(tags.iter().map(|tag| JournalEntry::Tag(tag.clone())));
+
+            for entry in entries {
+                journal.append(&entry)?;
+            }
+
+            return Ok(());
+        }
+
+        if let Some(log_queue) = &self.log_queue {
+            for metric in metrics {
+                log_queue.push(QueueEntry::Metric(metric.clone()));
+            }
+
+            for param in params {
+                log_queue.push(QueueEntry::Parameter(param.clone()));
+            }
+
+            for tag in tags {
+                log_queue.push(QueueEntry::Tag(tag.clone()));
+            }
+
+            return Ok(());
+        }
+
+        log_batch_request(&self.client, &self.info.run_id, metrics, params, tags)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn log_batch(&self, _: &[Metric], _: &[RunParameters], _: &[RunTag]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn log_metrics<K: AsRef<str>>(
+        &self,
+        metrics: &HashMap<K, f32>,
+        step: Option<u64>,
+    ) -> Result<(), Error> {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_millis() as u64;
+
+        let metrics: Vec<Metric> = metrics
+            .iter()
+            .map(|(key, value)| Metric {
+                key: key.as_ref().to_owned(),
+                value: *value,
+                timestamp,
+                step,
+            })
+            .collect();
 
+        self.log_batch(&metrics, &[], &[])
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn log_metrics<K: AsRef<str>>(
+        &self,
+        _: &HashMap<K, f32>,
+        _: Option<u64>,
+    ) -> Result<(), Error> {
         Ok(())
     }
 
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn log_parameters<K: AsRef<str>, V: AsRef<str>>(
+        &self,
+        params: &HashMap<K, V>,
+    ) -> Result<(), Error> {
+        let params: Vec<RunParameters> = params
+            .iter()
+            .map(|(key, value)| RunParameters {
+                key: key.as_ref().to_owned(),
+                value: value.as_ref().to_owned(),
+            })
+            .collect();
+
+        self.log_batch(&[], &params, &[])
+    }
+
     #[cfg(disable_experiment_tracking)]
-    pub fn log_parameter_struct_as_json<T: Serialize>(
+    pub fn log_parameters<K: AsRef<str>, V: AsRef<str>>(
         &self,
-        _: T,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        _: &HashMap<K, V>,
+    ) -> Result<(), Error> {
         Ok(())
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn list_artifacts(&self, prefix: &str) -> Result<Vec<ArtifactInfo>, Box<dyn std::error::Error>> {
-        let response = checked_get_request::<ListArtifactsRequest, ListArtifactsResponse>(
-            &format!("{}/api/2.0/mlflow/artifacts/list", self.api_root),
-            &ListArtifactsRequest {
-                run_id: self.info.run_id.clone(),
-                path: prefix.to_string()
-            },
+    pub fn log_parameter_struct_as_json<T: Serialize>(&self, parameters: T) -> Result<(), Error> {
+        let parsed = serde_json::to_value(parameters)?;
+        let mut params = Vec::new();
+        Self::collect_serde_value_as_parameters("", parsed, &mut params)?;
+
+        self.log_batch(&[], &params, &[])
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn log_parameter_struct_as_json<T: Serialize>(&self, _: T) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Registers the model files at the artifact path as a new version of the registered model
+    /// and waits until the version is ready. The registered model is created if it does not exist yet.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn register_model(&self, artifact_path: &str, name: &str) -> Result<ModelVersion, Error> {
+        if self.journal.is_some() {
+            return Err(Error::InvalidInput(
+                "the model of an offline run can only be registered after the run was synchronized"
+                    .to_owned(),
+            ));
+        }
+
+        match RegisteredModel::create(&self.client, name, None, vec![]) {
+            Ok(_) => {}
+            Err(e) if e.error_code() == Some(&ErrorCode::ResourceAlreadyExists) => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut version = ModelVersion::create(
+            &self.client,
+            name,
+            &format!(
+                "runs:/{}/{}",
+                self.info.run_id,
+                artifact_path.trim_start_matches('/')
+            ),
+            Some(&self.info.run_id),
+            vec![],
         )?;
 
-        Ok(response.files)
+        version.wait_until_ready(MODEL_REGISTRATION_TIMEOUT)?;
+
+        Ok(version)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn list_artifacts(&self, prefix: &str) -> Result<Vec<ArtifactInfo>, Box<dyn std::error::Error>> {
+    pub fn register_model(&self, _: &str, _: &str) -> Result<ModelVersion, Error> {
+        Ok(ModelVersion::default())
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn list_artifacts(&self, prefix: &str) -> Result<Vec<ArtifactInfo>, Error> {
+        self.get_artifact_repository()?.list_artifacts(prefix)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn list_artifacts(&self, prefix: &str) -> Result<Vec<ArtifactInfo>, Error> {
         Ok(Vec::new())
     }
 
+    /// Deletes the file or directory. This is not supported by the legacy endpoints of the tracking server.
     #[cfg(not(disable_experiment_tracking))]
-    fn log_serde_value_as_parameters(
-        &self,
+    pub fn delete_artifact(&self, path: &str) -> Result<(), Error> {
+        self.get_artifact_repository()?.delete_artifact(path)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn delete_artifact(&self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// The repository that is used for the artifacts of the run, selected by the artifact backend of the client.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn get_artifact_repository(&self) -> Result<Box<dyn ArtifactRepository>, Error> {
+        if let Some(journal) = &self.journal {
+            return Ok(Box::new(LocalArtifactRepository::new(
+                &journal.artifacts_dir(),
+            )));
+        }
+
+        artifact_repository(&self.client, &self.info.run_id, &self.info.artifact_uri)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn get_artifact_repository(&self) -> Result<Box<dyn ArtifactRepository>, Error> {
+        Err(Error::TrackingDisabled)
+    }
+
+    /// Records an artifact of an offline run, it is uploaded by the synchronization.
+    fn journal_artifact(&self, artifact_path: &str) -> Result<(), Error> {
+        match &self.journal {
+            Some(journal) => journal.append(&JournalEntry::Artifact {
+                path: artifact_path.trim_matches('/').to_owned(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub(crate) fn collect_serde_value_as_parameters(
         prefix: &str,
         value: Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        params: &mut Vec<RunParameters>,
+    ) -> Result<(), Error> {
         let map = match value {
             Value::Object(map) => Ok(map),
-            _ => Err(NotAMapError),
+            _ => Err(Error::InvalidInput("value is not a map".to_owned())),
         }?;
 
         for (k, v) in map {
             match v {
                 Value::Object(_) => {
-                    self.log_serde_value_as_parameters(&format!("{prefix}{k}/"), v)?;
+                    Self::collect_serde_value_as_parameters(&format!("{prefix}{k}/"), v, params)?;
                 }
                 _ => {
-                    self.log_parameter(&format!("{prefix}{k}"), &v.to_string())?;
+                    params.push(RunParameters {
+                        key: format!("{prefix}{k}"),
+                        value: v.to_string(),
+                    });
                 }
             }
         }
@@ -245,75 +860,343 @@ impl Run {
         Ok(())
     }
 
-    #[cfg(disable_experiment_tracking)]
-    fn log_serde_value_as_parameters(
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn log_artifact_file(
         &self,
-        prefix: &str,
-        value: Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        path_on_disk: &Path,
+        path_destination: &str,
+    ) -> Result<(), Error> {
+        self.get_artifact_repository()?
+            .log_artifact_file(path_on_disk, path_destination)?;
+        self.journal_artifact(path_destination)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn log_artifact_file(&self, _: &Path, _: &str) -> Result<(), Error> {
         Ok(())
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn log_artifact_file(
+    pub fn log_artifact_bytes(&self, data: Vec<u8>, path_destination: &str) -> Result<(), Error> {
+        self.get_artifact_repository()?
+            .log_artifact_bytes(data, path_destination)?;
+        self.journal_artifact(path_destination)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn log_artifact_bytes(&self, _: Vec<u8>, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Uploads the model files and an `MLmodel` descriptor into the artifact directory `artifact_path`.
+    /// Files are placed directly in the directory and directories are uploaded with their content below their name,
+    /// e.g. `tokenizer/vocab.json`. Two paths with the same name are rejected.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn log_model(
+        &mut self,
+        artifact_path: &str,
+        model_files: &[&Path],
+        flavors: BTreeMap<String, Value>,
+        signature: Option<ModelSignature>,
+    ) -> Result<ModelDescriptor, Error> {
+        let artifact_path = artifact_path.trim_matches('/');
+        let mut uploads = BTreeMap::new();
+
+        // checked before the upload, so a rejected model leaves no files behind
+        for &path in model_files {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .filter(|&name| name != MLMODEL_FILE_NAME)
+                .ok_or_else(|| {
+                    Error::InvalidInput(format!("{} cannot be a model file", path.display()))
+                })?;
+
+            let files = if path.is_dir() {
+                collect_files(path, &UploadDirOptions::default())?
+                    .into_iter()
+                    .map(|(file, relative_path)| (file, format!("{name}/{relative_path}")))
+                    .collect()
+            } else {
+                vec![(path.to_path_buf(), name.to_owned())]
+            };
+
+            for (file, relative_path) in files {
+                if uploads.insert(relative_path.clone(), file).is_some() {
+                    return Err(Error::InvalidInput(format!(
+                        "the model contains more than one file {relative_path}"
+                    )));
+                }
+            }
+        }
+
+        for (relative_path, file) in &uploads {
+            self.log_artifact_file(file, &format!("{artifact_path}/{relative_path}"))?;
+        }
+
+        let descriptor = ModelDescriptor {
+            artifact_path: artifact_path.to_owned(),
+            flavors,
+            run_id: self.info.run_id.clone(),
+            utc_time_created: chrono::Utc::now()
+                .format("%Y-%m-%d %H:%M:%S%.6f")
+                .to_string(),
+            signature,
+            extra: BTreeMap::new(),
+        };
+
+        self.log_artifact_bytes(
+            descriptor.to_yaml()?.into_bytes(),
+            &format!("{artifact_path}/{MLMODEL_FILE_NAME}"),
+        )?;
+
+        let mut logged_models: Vec<Value> = self
+            .data
+            .tags
+            .iter()
+            .find(|tag| tag.key == LOGGED_MODELS_TAG)
+            .and_then(|tag| serde_json::from_str(&tag.value).ok())
+            .unwrap_or_default();
+        logged_models.push(serde_json::to_value(&descriptor)?);
+
+        self.set_tag(LOGGED_MODELS_TAG, &serde_json::to_string(&logged_models)?)?;
+
+        Ok(descriptor)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn log_model(
+        &mut self,
+        _: &str,
+        _: &[&Path],
+        _: BTreeMap<String, Value>,
+        _: Option<ModelSignature>,
+    ) -> Result<ModelDescriptor, Error> {
+        Ok(ModelDescriptor::default())
+    }
+
+    /// Downloads the artifact directory of a model logged with [`Run::log_model`] into `destination`
+    /// and returns its parsed `MLmodel` descriptor.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn load_model(
         &self,
-        path_on_disk: &Path,
-        path_destination: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let client = reqwest::blocking::Client::new();
-        let file = std::fs::File::open(path_on_disk)?;
+        artifact_path: &str,
+        destination: &Path,
+    ) -> Result<ModelDescriptor, Error> {
+        let artifact_path = artifact_path.trim_matches('/');
+
+        fs::create_dir_all(destination)?;
+        self.download_artifact_tree(
+            self.list_artifacts(artifact_path)?,
+            destination,
+            &mut Vec::new(),
+        )?;
+
+        ModelDescriptor::from_yaml(&fs::read_to_string(destination.join(MLMODEL_FILE_NAME))?)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn load_model(&self, _: &str, _: &Path) -> Result<ModelDescriptor, Error> {
+        Err(Error::TrackingDisabled)
+    }
+
+    /// Downloads the file or directory at `artifact_path` into `local_dir` and keeps its path relative to the artifact root,
+    /// e.g. `checkpoints/epoch-1.bin` ends up at `<local_dir>/checkpoints/epoch-1.bin`. All artifacts are downloaded if the path is empty.
+    /// Returns the local paths of the downloaded files, an empty directory is only created locally.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn download_artifacts(
+        &self,
+        artifact_path: &str,
+        local_dir: &Path,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let artifact_path = artifact_path.trim_matches('/');
+        let mut destination = local_dir.to_path_buf();
+
+        for name in artifact_path.split('/').filter(|name| !name.is_empty()) {
+            destination.push(local_name(artifact_path, name)?);
+        }
+
+        let mut paths = Vec::new();
+        let infos = self.list_artifacts(artifact_path)?;
+
+        // the server returns an empty listing for files, empty directories and missing paths alike
+        if infos.is_empty() && !artifact_path.is_empty() {
+            let (parent, _) = artifact_path.rsplit_once('/').unwrap_or_default();
+            let info = self
+                .list_artifacts(parent)?
+                .into_iter()
+                .find(|info| info.path.trim_matches('/') == artifact_path)
+                .ok_or_else(|| {
+                    Error::InvalidInput(format!("the artifact {artifact_path} does not exist"))
+                })?;
+
+            if info.is_dir {
+                fs::create_dir_all(&destination)?;
+            } else {
+                self.download_artifact_to_file(artifact_path, &destination, false, |_| {})?;
+                paths.push(destination);
+            }
+        } else {
+            fs::create_dir_all(&destination)?;
+            self.download_artifact_tree(infos, &destination, &mut paths)?;
+        }
+
+        Ok(paths)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn download_artifacts(&self, _: &str, _: &Path) -> Result<Vec<PathBuf>, Error> {
+        Err(Error::TrackingDisabled)
+    }
 
-        client
-            .post(format!(
-                "{}/ajax-api/2.0/mlflow/upload-artifact",
-                self.api_root
-            ))
-            .body(file)
-            .query(&[
-                ("run_uuid", self.info.run_id.as_str()),
-                ("path", path_destination),
-            ])
-            .send()?
-            .error_for_status()?;
+    #[cfg(not(disable_experiment_tracking))]
+    fn download_artifact_tree(
+        &self,
+        infos: Vec<ArtifactInfo>,
+        destination: &Path,
+        paths: &mut Vec<PathBuf>,
+    ) -> Result<(), Error> {
+        for info in infos {
+            let name = info.path.rsplit('/').next().unwrap_or(&info.path);
+            let local_path = destination.join(local_name(&info.path, name)?);
+
+            if info.is_dir {
+                fs::create_dir_all(&local_path)?;
+                self.download_artifact_tree(self.list_artifacts(&info.path)?, &local_path, paths)?;
+            } else {
+                self.download_artifact_to_file(&info.path, &local_path, false, |_| {})?;
+                paths.push(local_path);
+            }
+        }
 
         Ok(())
     }
 
+    /// Uploads all files of the directory that match the options and keeps their relative paths below `artifact_subpath`.
+    /// A failed upload does not stop the others, the summary lists which files were uploaded and which failed.
+    /// Symbolic links to files are uploaded as files, symbolic links to directories are skipped.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn log_artifacts_dir(
+        &self,
+        local_dir: &Path,
+        artifact_subpath: &str,
+        options: &UploadDirOptions,
+    ) -> Result<UploadSummary, Error> {
+        let artifact_subpath = artifact_subpath.trim_matches('/');
+        let files = collect_files(local_dir, options)?;
+        let next_file = AtomicUsize::new(0);
+        let summary = Mutex::new(UploadSummary::default());
+
+        let upload_files = || {
+            while let Some((path, relative_path)) =
+                files.get(next_file.fetch_add(1, Ordering::Relaxed))
+            {
+                let artifact_path = if artifact_subpath.is_empty() {
+                    relative_path.clone()
+                } else {
+                    format!("{artifact_subpath}/{relative_path}")
+                };
+
+                let result = self.log_artifact_file(path, &artifact_path);
+                let mut summary = summary.lock().unwrap_or_else(|e| e.into_inner());
+
+                match result {
+                    Ok(()) => summary.uploaded.push(artifact_path),
+                    Err(e) => summary.failed.push((artifact_path, e)),
+                }
+            }
+        };
+
+        thread::scope(|scope| {
+            for _ in 1..options.max_parallel_uploads.min(files.len()) {
+                scope.spawn(upload_files);
+            }
+
+            upload_files();
+        });
+
+        let mut summary = summary.into_inner().unwrap_or_else(|e| e.into_inner());
+        // parallel uploads finish in any order
+        summary.uploaded.sort();
+        summary.failed.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(summary)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn log_artifacts_dir(
+        &self,
+        _: &Path,
+        _: &str,
+        _: &UploadDirOptions,
+    ) -> Result<UploadSummary, Error> {
+        Ok(UploadSummary::default())
+    }
+
+    /// Streams the data from the reader to the server without holding it in memory.
+    /// Unlike the other uploads this one cannot be retried.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn log_artifact_reader<R: Read + Send + 'static>(
+        &self,
+        reader: R,
+        path_destination: &str,
+    ) -> Result<(), Error> {
+        self.get_artifact_repository()?
+            .log_artifact_reader(Box::new(reader), path_destination)?;
+        self.journal_artifact(path_destination)
+    }
+
     #[cfg(disable_experiment_tracking)]
-    pub fn log_artifact_file(&self, _: &Path, _: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn log_artifact_reader<R: Read + Send + 'static>(
+        &self,
+        _: R,
+        _: &str,
+    ) -> Result<(), Error> {
         Ok(())
     }
 
+    /// Uploads the file in parts directly to the storage behind the artifact proxy of the tracking server.
+    /// This requires a server that proxies artifacts to a storage with multipart support, like S3, GCS or Azure.
+    /// With [`ArtifactBackend::Local`] the file is copied like by `log_artifact_file`,
+    /// with [`ArtifactBackend::Legacy`] an error is returned.
     #[cfg(not(disable_experiment_tracking))]
-    pub fn log_artifact_bytes(
+    pub fn log_artifact_file_multipart(
         &self,
-        data: Vec<u8>,
+        path_on_disk: &Path,
         path_destination: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let client = reqwest::blocking::Client::new();
+        config: &MultipartUploadConfig,
+    ) -> Result<(), Error> {
+        // the artifacts of an offline run are uploaded by the synchronization
+        if self.journal.is_some() {
+            return self.log_artifact_file(path_on_disk, path_destination);
+        }
+
+        match self.client.get_artifact_backend() {
+            ArtifactBackend::Auto | ArtifactBackend::Proxy => {}
+            ArtifactBackend::Local => {
+                return self.log_artifact_file(path_on_disk, path_destination)
+            }
+            ArtifactBackend::Legacy => {
+                return Err(Error::InvalidInput(
+                    "multipart uploads require the artifact proxy, but the client uses the legacy endpoints".to_owned(),
+                ))
+            }
+        }
 
-        client
-            .post(format!(
-                "{}/ajax-api/2.0/mlflow/upload-artifact",
-                self.api_root
-            ))
-            .body(data)
-            .query(&[
-                ("run_uuid", self.info.run_id.as_str()),
-                ("path", path_destination),
-            ])
-            .send()?
-            .error_for_status()?;
+        let proxied_path = proxied_artifact_path(&self.info.artifact_uri, path_destination)?;
+        let file = File::open(path_on_disk)?;
+        let size = file.metadata()?.len();
 
-        Ok(())
+        multipart_upload(&self.client, &proxied_path, file, size, config)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn log_artifact_bytes(
+    pub fn log_artifact_file_multipart(
         &self,
-        _: Vec<u8>,
+        _: &Path,
         _: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        _: &MultipartUploadConfig,
+    ) -> Result<(), Error> {
         Ok(())
     }
 
@@ -322,7 +1205,7 @@ impl Run {
         &self,
         data_struct: T,
         path_destination: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), Error> {
         let data = serde_json::to_string(&data_struct)?.into_bytes();
 
         self.log_artifact_bytes(data, path_destination)?;
@@ -335,7 +1218,7 @@ impl Run {
         &self,
         data_struct: T,
         path_destination: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), Error> {
         Ok(())
     }
 
@@ -344,7 +1227,7 @@ impl Run {
         &self,
         data_struct: T,
         path_destination: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), Error> {
         let data = bincode::serialize(&data_struct)?;
 
         self.log_artifact_bytes(data, path_destination)?;
@@ -357,34 +1240,24 @@ impl Run {
         &self,
         data_struct: T,
         path_destination: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), Error> {
         Ok(())
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn log_logger<L: Log + 'static>(
-        &self,
-        logger: &ExperimentLogger<L>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn log_logger<L: Log + 'static>(&self, logger: &ExperimentLogger<L>) -> Result<(), Error> {
         self.log_artifact_bytes(logger.to_string().into_bytes(), "log.log")
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn log_logger<L: Log + 'static>(
-        &self,
-        _: &ExperimentLogger<L>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn log_logger<L: Log + 'static>(&self, _: &ExperimentLogger<L>) -> Result<(), Error> {
         Ok(())
     }
 
-    pub fn run_experiment<F, A>(
-        &mut self,
-        experiment_function: F,
-        args: A
-    ) -> Result<(), Box<dyn std::error::Error>>
+    pub fn run_experiment<F, A>(&mut self, experiment_function: F, args: A) -> Result<(), Error>
     where
         F: Fn(&Run, Arc<AtomicBool>, A) -> Result<(), Box<dyn std::error::Error>> + RefUnwindSafe,
-        A: UnwindSafe
+        A: UnwindSafe,
     {
         let was_killed = Arc::new(AtomicBool::new(false));
         let was_killed_clone = was_killed.clone();
@@ -432,7 +1305,7 @@ impl Run {
         experiment_function: F,
         args: A,
         logger: L,
-    ) -> Result<(), Box<dyn std::error::Error>>
+    ) -> Result<(), Error>
     where
         F: Fn(&Run, Arc<AtomicBool>, A) -> Result<(), Box<dyn std::error::Error>> + RefUnwindSafe,
         A: UnwindSafe
@@ -446,37 +1319,108 @@ impl Run {
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn get_artifact_as_bytes(&self, path: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .get(format!("{}/get-artifact", self.api_root))
-            .query(&[("path", path), ("run_uuid", self.info.run_id.as_str())])
-            .send()?
-            .error_for_status()?;
-
-        Ok(response.bytes()?.into())
+    pub fn get_artifact_as_bytes(&self, path: &str) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::new();
+        self.get_artifact_repository()?
+            .open_artifact(path, 0)?
+            .reader
+            .read_to_end(&mut data)?;
+
+        Ok(data)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn get_artifact_as_bytes(&self, _: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        Err(ArtifactDownloadError)?
+    pub fn get_artifact_as_bytes(&self, _: &str) -> Result<Vec<u8>, Error> {
+        Err(Error::TrackingDisabled)
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn get_artifact_as_string(&self, path: &str) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn get_artifact_as_string(&self, path: &str) -> Result<String, Error> {
         Ok(String::from_utf8(self.get_artifact_as_bytes(path)?)?)
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn get_artifact_as_string(&self, _: &str) -> Result<String, Box<dyn std::error::Error>> {
-        Err(ArtifactDownloadError)?
+    pub fn get_artifact_as_string(&self, _: &str) -> Result<String, Error> {
+        Err(Error::TrackingDisabled)
     }
 
+    /// Streams the artifact into the writer without keeping it in memory and returns the number of written bytes.
     #[cfg(not(disable_experiment_tracking))]
-    pub fn get_artifact_binary_as_struct<T>(
+    pub fn download_artifact_to_writer(
         &self,
         path: &str,
-    ) -> Result<T, Box<dyn std::error::Error>>
+        writer: &mut impl Write,
+        mut on_progress: impl FnMut(DownloadProgress),
+    ) -> Result<u64, Error> {
+        let mut stream = self.get_artifact_repository()?.open_artifact(path, 0)?;
+        let progress = DownloadProgress {
+            bytes_done: 0,
+            total_bytes: stream.total_bytes,
+        };
+
+        copy_with_progress(&mut stream.reader, writer, progress, &mut on_progress)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn download_artifact_to_writer(
+        &self,
+        _: &str,
+        _: &mut impl Write,
+        _: impl FnMut(DownloadProgress),
+    ) -> Result<u64, Error> {
+        Err(Error::TrackingDisabled)
+    }
+
+    /// Streams the artifact into the file and returns its size.
+    /// With `resume`, only the bytes that are missing from an existing partial file are requested, nothing if it is complete already.
+    /// The file is downloaded again from the start if the repository cannot continue it, e.g. if the server does not support range requests.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn download_artifact_to_file(
+        &self,
+        path: &str,
+        local_path: &Path,
+        resume: bool,
+        mut on_progress: impl FnMut(DownloadProgress),
+    ) -> Result<u64, Error> {
+        if let Some(parent) = local_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let offset = match fs::metadata(local_path) {
+            Ok(metadata) if resume && metadata.is_file() => metadata.len(),
+            _ => 0,
+        };
+
+        let mut stream = self
+            .get_artifact_repository()?
+            .open_artifact(path, offset)?;
+        let progress = DownloadProgress {
+            bytes_done: stream.offset,
+            total_bytes: stream.total_bytes,
+        };
+
+        let mut file = if stream.offset > 0 {
+            OpenOptions::new().append(true).open(local_path)?
+        } else {
+            File::create(local_path)?
+        };
+
+        copy_with_progress(&mut stream.reader, &mut file, progress, &mut on_progress)
+    }
+
+    #[cfg(disable_experiment_tracking)]
+    pub fn download_artifact_to_file(
+        &self,
+        _: &str,
+        _: &Path,
+        _: bool,
+        _: impl FnMut(DownloadProgress),
+    ) -> Result<u64, Error> {
+        Err(Error::TrackingDisabled)
+    }
+
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn get_artifact_binary_as_struct<T>(&self, path: &str) -> Result<T, Error>
     where
         T: for<'de> Deserialize<'de>,
     {
@@ -486,21 +1430,15 @@ impl Run {
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn get_artifact_binary_as_struct<T>(
-        &self,
-        path: &str,
-    ) -> Result<T, Box<dyn std::error::Error>>
+    pub fn get_artifact_binary_as_struct<T>(&self, path: &str) -> Result<T, Error>
     where
         T: for<'de> Deserialize<'de>,
     {
-        Err(ArtifactDownloadError)?
+        Err(Error::TrackingDisabled)
     }
 
     #[cfg(not(disable_experiment_tracking))]
-    pub fn get_artifact_json_as_struct<T>(
-        &self,
-        path: &str,
-    ) -> Result<T, Box<dyn std::error::Error>>
+    pub fn get_artifact_json_as_struct<T>(&self, path: &str) -> Result<T, Error>
     where
         T: for<'de> Deserialize<'de>,
     {
@@ -510,22 +1448,79 @@ impl Run {
     }
 
     #[cfg(disable_experiment_tracking)]
-    pub fn get_artifact_json_as_struct<T>(
-        &self,
-        path: &str,
-    ) -> Result<T, Box<dyn std::error::Error>>
+    pub fn get_artifact_json_as_struct<T>(&self, path: &str) -> Result<T, Error>
     where
         T: for<'de> Deserialize<'de>,
     {
-        Err(ArtifactDownloadError)?
+        Err(Error::TrackingDisabled)
     }
 
-    pub fn get_api_root(&self) -> &str {
-        &self.api_root
+    /// Returns every logged value of the metric, requesting further pages if necessary.
+    #[cfg(not(disable_experiment_tracking))]
+    pub fn get_metric_history(&self, key: &str) -> Result<Vec<Metric>, Error> {
+        if self.journal.is_some() {
+            return Err(Error::InvalidInput(
+                "the metric history of an offline run can only be read after the run was synchronized"
+                    .to_owned(),
+            ));
+        }
+
+        self.flush()?;
+
+        Paginated::new(|page_token| {
+            let response: GetMetricHistoryResponse = self.client.checked_get_request(
+                "api/2.0/mlflow/metrics/get-history",
+                &GetMetricHistoryRequest {
+                    run_id: self.info.run_id.clone(),
+                    metric_key: key.to_owned(),
+                    page_token,
+                },
+            )?;
+
+            Ok((response.metrics, response.next_page_token))
+        })
+        .collect()
     }
 
-    pub fn set_api_root(&mut self, api_root: &str) {
-        self.api_root = api_root.to_owned()
+    #[cfg(disable_experiment_tracking)]
+    pub fn get_metric_history(&self, _: &str) -> Result<Vec<Metric>, Error> {
+        Ok(vec![])
+    }
+
+    /// Returns the history of each of the metrics by their key.
+    /// The server has no endpoint for several keys, so the histories are requested one key after another.
+    pub fn get_metric_histories(
+        &self,
+        keys: &[&str],
+    ) -> Result<HashMap<String, Vec<Metric>>, Error> {
+        keys.iter()
+            .map(|&key| Ok((key.to_owned(), self.get_metric_history(key)?)))
+            .collect()
+    }
+
+    pub(crate) fn new_offline(info: RunInfo, tags: Vec<RunTag>, journal: Journal) -> Self {
+        Self {
+            journal: Some(journal),
+            info,
+            data: RunData {
+                tags,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Whether the run only exists in a local journal, see [`crate::offline`].
+    pub fn is_offline(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    pub fn get_client(&self) -> &MlflowClient {
+        &self.client
+    }
+
+    pub fn set_client(&mut self, client: &MlflowClient) {
+        self.client = client.clone()
     }
 
     pub fn get_run_uuid(&self) -> &str {
@@ -567,4 +1562,177 @@ impl Run {
     pub fn get_parameters(&self) -> &Vec<RunParameters> {
         &self.data.params
     }
+
+    pub fn get_dataset_inputs(&self) -> &Vec<DatasetInput> {
+        &self.inputs.dataset_inputs
+    }
+
+    /// The latest value of each metric at the time the run was fetched from the server.
+    pub fn get_metrics(&self) -> &Vec<Metric> {
+        &self.data.metrics
+    }
+
+    pub fn get_metric(&self, key: &str) -> Option<&Metric> {
+        self.data.metrics.iter().find(|metric| metric.key == key)
+    }
+}
+
+pub(crate) fn log_batch_request(
+    client: &MlflowClient,
+    run_id: &str,
+    metrics: &[Metric],
+    params: &[RunParameters],
+    tags: &[RunTag],
+) -> Result<(), Error> {
+    for request in split_log_batch(run_id, metrics, params, tags) {
+        client.checked_post_request::<LogBatchRequest, LogBatchResponse>(
+            "api/2.0/mlflow/runs/log-batch",
+            &request,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Splits the data into requests that don't exceed the limits of the log-batch endpoint.
+pub(crate) fn split_log_batch<'a>(
+    run_id: &str,
+    metrics: &'a [Metric],
+    params: &'a [RunParameters],
+    tags: &'a [RunTag],
+) -> Vec<LogBatchRequest<'a>> {
+    let (mut metrics, mut params, mut tags) = (metrics, params, tags);
+    let mut requests = Vec::new();
+
+    while !metrics.is_empty() || !params.is_empty() || !tags.is_empty() {
+        let (params_chunk, params_rest) = params.split_at(params.len().min(MAX_PARAMS_PER_BATCH));
+        let (tags_chunk, tags_rest) = tags.split_at(tags.len().min(MAX_TAGS_PER_BATCH));
+        let metrics_limit = MAX_METRICS_PER_BATCH
+            .min(MAX_ENTITIES_PER_BATCH - params_chunk.len() - tags_chunk.len());
+        let (metrics_chunk, metrics_rest) = metrics.split_at(metrics.len().min(metrics_limit));
+
+        requests.push(LogBatchRequest {
+            run_id: run_id.to_owned(),
+            metrics: metrics_chunk,
+            params: params_chunk,
+            tags: tags_chunk,
+        });
+
+        metrics = metrics_rest;
+        params = params_rest;
+        tags = tags_rest;
+    }
+
+    requests
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(count: usize) -> Vec<Metric> {
+        (0..count)
+            .map(|i| Metric {
+                key: format!("m{i}"),
+                value: i as f32,
+                timestamp: 0,
+                step: None,
+            })
+            .collect()
+    }
+
+    fn params(count: usize) -> Vec<RunParameters> {
+        (0..count)
+            .map(|i| RunParameters {
+                key: format!("p{i}"),
+                value: i.to_string(),
+            })
+            .collect()
+    }
+
+    fn tags(count: usize) -> Vec<RunTag> {
+        (0..count)
+            .map(|i| RunTag {
+                key: format!("t{i}"),
+                value: i.to_string(),
+            })
+            .collect()
+    }
+
+    fn chunk_sizes(requests: &[LogBatchRequest]) -> Vec<(usize, usize, usize)> {
+        requests
+            .iter()
+            .map(|request| {
+                (
+                    request.metrics.len(),
+                    request.params.len(),
+                    request.tags.len(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn split_log_batch_without_data() {
+        assert!(split_log_batch("r", &[], &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn split_log_batch_at_the_limits() {
+        let (metrics, params, tags) = (metrics(1000), params(100), tags(100));
+        let requests = split_log_batch("r", &metrics[..900], &params, &[]);
+
+        assert_eq!(chunk_sizes(&requests), [(900, 100, 0)]);
+
+        let requests = split_log_batch("r", &metrics, &[], &[]);
+
+        assert_eq!(chunk_sizes(&requests), [(1000, 0, 0)]);
+
+        let requests = split_log_batch("r", &[], &params, &tags);
+
+        assert_eq!(chunk_sizes(&requests), [(0, 100, 100)]);
+    }
+
+    #[test]
+    fn split_log_batch_above_the_limits() {
+        let (metrics, params, tags) = (metrics(2500), params(101), tags(201));
+        let requests = split_log_batch("r", &metrics, &[], &[]);
+
+        assert_eq!(
+            chunk_sizes(&requests),
+            [(1000, 0, 0), (1000, 0, 0), (500, 0, 0)]
+        );
+
+        let requests = split_log_batch("r", &[], &params, &tags);
+
+        assert_eq!(
+            chunk_sizes(&requests),
+            [(0, 100, 100), (0, 1, 100), (0, 0, 1)]
+        );
+    }
+
+    #[test]
+    fn split_log_batch_respects_the_combined_limit() {
+        let (metrics, params, tags) = (metrics(1000), params(250), tags(150));
+        let requests = split_log_batch("r", &metrics, &params, &tags);
+
+        // params and tags reduce the number of metrics so that each request has at most 1000 entries
+        assert_eq!(
+            chunk_sizes(&requests),
+            [(800, 100, 100), (200, 100, 50), (0, 50, 0)]
+        );
+        assert!(requests.iter().all(|request| request.run_id == "r"));
+
+        let sent_metrics = requests
+            .iter()
+            .flat_map(|request| request.metrics)
+            .map(|metric| metric.key.as_str())
+            .collect::<Vec<_>>();
+        let expected_metrics = metrics
+            .iter()
+            .map(|metric| metric.key.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(sent_metrics, expected_metrics);
+    }
 }
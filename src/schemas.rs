@@ -1,14 +1,21 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    experiment::Experiment,
-    run::{ArtifactInfo, Run, RunInfo, RunTag, Status},
+    dataset::DatasetInput,
+    experiment::{Experiment, ExperimentTag, ViewType},
+    registry::{
+        model_version::{ModelVersion, ModelVersionTag, Stage},
+        registered_model::{RegisteredModel, RegisteredModelTag},
+    },
+    run::{ArtifactInfo, Metric, Run, RunInfo, RunParameters, RunTag, Status},
 };
 
 #[derive(Serialize)]
 pub(crate) struct CreateExperimentRequest {
     pub(crate) name: String,
-    pub(crate) tags: Vec<String>,
+    pub(crate) tags: Vec<ExperimentTag>,
 }
 
 #[derive(Deserialize)]
@@ -27,10 +34,73 @@ pub(crate) struct GetExperimentByNameRequest {
 }
 
 #[derive(Deserialize)]
-pub(crate) struct GetExperimentResponse {
-    pub(crate) experiment: Experiment,
+pub(crate) struct GetExperimentResponse<E = Experiment> {
+    pub(crate) experiment: E,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SearchExperimentsRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) max_results: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) page_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) filter: Option<String>,
+    pub(crate) order_by: Vec<String>,
+    pub(crate) view_type: ViewType,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SearchExperimentsResponse {
+    #[serde(default)]
+    pub(crate) experiments: Vec<Experiment>,
+    pub(crate) next_page_token: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SetExperimentTagRequest {
+    pub(crate) experiment_id: String,
+    pub(crate) key: String,
+    pub(crate) value: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SetExperimentTagResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteExperimentTagRequest {
+    pub(crate) experiment_id: String,
+    pub(crate) key: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteExperimentTagResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteExperimentRequest {
+    pub(crate) experiment_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteExperimentResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct UpdateExperimentRequest {
+    pub(crate) experiment_id: String,
+    pub(crate) new_name: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct UpdateExperimentResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct RestoreExperimentRequest {
+    pub(crate) experiment_id: String,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct RestoreExperimentResponse {}
+
 #[derive(Serialize)]
 pub(crate) struct CreateRunRequest {
     pub(crate) experiment_id: String,
@@ -40,8 +110,8 @@ pub(crate) struct CreateRunRequest {
 }
 
 #[derive(Deserialize)]
-pub(crate) struct CreateRunResponse {
-    pub(crate) run: Run,
+pub(crate) struct CreateRunResponse<R = Run> {
+    pub(crate) run: R,
 }
 
 #[derive(Serialize)]
@@ -50,10 +120,46 @@ pub(crate) struct GetRunRequest {
 }
 
 #[derive(Deserialize)]
-pub(crate) struct GetRunResponse {
-    pub(crate) run: Run,
+pub(crate) struct GetRunResponse<R = Run> {
+    pub(crate) run: R,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SearchRunsRequest {
+    pub(crate) experiment_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) filter: Option<String>,
+    pub(crate) run_view_type: ViewType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) max_results: Option<u32>,
+    pub(crate) order_by: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SearchRunsResponse {
+    #[serde(default)]
+    pub(crate) runs: Vec<Run>,
+    pub(crate) next_page_token: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteRunRequest {
+    pub(crate) run_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteRunResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct RestoreRunRequest {
+    pub(crate) run_id: String,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct RestoreRunResponse {}
+
 #[derive(Serialize)]
 pub(crate) struct UpdateRunRequest {
     pub(crate) run_id: String,
@@ -88,13 +194,287 @@ pub(crate) struct LogParameterRequest {
 #[derive(Deserialize)]
 pub(crate) struct LogParameterResponse {}
 
+#[derive(Serialize)]
+pub(crate) struct LogInputsRequest<'a> {
+    pub(crate) run_id: String,
+    pub(crate) datasets: &'a [DatasetInput],
+}
+
+#[derive(Deserialize)]
+pub(crate) struct LogInputsResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct SetTagRequest {
+    pub(crate) run_id: String,
+    pub(crate) key: String,
+    pub(crate) value: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SetTagResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteTagRequest {
+    pub(crate) run_id: String,
+    pub(crate) key: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteTagResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct LogBatchRequest<'a> {
+    pub(crate) run_id: String,
+    pub(crate) metrics: &'a [Metric],
+    pub(crate) params: &'a [RunParameters],
+    pub(crate) tags: &'a [RunTag],
+}
+
+#[derive(Deserialize)]
+pub(crate) struct LogBatchResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct GetMetricHistoryRequest {
+    pub(crate) run_id: String,
+    pub(crate) metric_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct GetMetricHistoryResponse {
+    #[serde(default)]
+    pub(crate) metrics: Vec<Metric>,
+    pub(crate) next_page_token: Option<String>,
+}
+
 #[derive(Serialize)]
 pub(crate) struct ListArtifactsRequest {
     pub(crate) run_id: String,
     pub(crate) path: String,
 }
 
+#[derive(Serialize)]
+pub(crate) struct ListProxiedArtifactsRequest {
+    pub(crate) path: String,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct ListArtifactsResponse {
+    #[serde(default)]
     pub(crate) files: Vec<ArtifactInfo>,
 }
+
+#[derive(Serialize)]
+pub(crate) struct CreateRegisteredModelRequest {
+    pub(crate) name: String,
+    pub(crate) tags: Vec<RegisteredModelTag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct GetRegisteredModelRequest {
+    pub(crate) name: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct RenameRegisteredModelRequest {
+    pub(crate) name: String,
+    pub(crate) new_name: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct UpdateRegisteredModelRequest {
+    pub(crate) name: String,
+    pub(crate) description: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RegisteredModelResponse {
+    pub(crate) registered_model: RegisteredModel,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteRegisteredModelRequest {
+    pub(crate) name: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteRegisteredModelResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct SearchRegisteredModelsRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) max_results: Option<u32>,
+    pub(crate) order_by: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SearchRegisteredModelsResponse {
+    #[serde(default)]
+    pub(crate) registered_models: Vec<RegisteredModel>,
+    pub(crate) next_page_token: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SetRegisteredModelTagRequest {
+    pub(crate) name: String,
+    pub(crate) key: String,
+    pub(crate) value: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SetRegisteredModelTagResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteRegisteredModelTagRequest {
+    pub(crate) name: String,
+    pub(crate) key: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteRegisteredModelTagResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct SetRegisteredModelAliasRequest {
+    pub(crate) name: String,
+    pub(crate) alias: String,
+    pub(crate) version: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SetRegisteredModelAliasResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteRegisteredModelAliasRequest {
+    pub(crate) name: String,
+    pub(crate) alias: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteRegisteredModelAliasResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct CreateModelVersionRequest {
+    pub(crate) name: String,
+    pub(crate) source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) run_id: Option<String>,
+    pub(crate) tags: Vec<ModelVersionTag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct GetModelVersionRequest {
+    pub(crate) name: String,
+    pub(crate) version: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct GetModelVersionByAliasRequest {
+    pub(crate) name: String,
+    pub(crate) alias: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct UpdateModelVersionRequest {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) description: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct TransitionModelVersionStageRequest {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) stage: Stage,
+    pub(crate) archive_existing_versions: bool,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ModelVersionResponse {
+    pub(crate) model_version: ModelVersion,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteModelVersionRequest {
+    pub(crate) name: String,
+    pub(crate) version: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteModelVersionResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct SetModelVersionTagRequest {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) key: String,
+    pub(crate) value: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SetModelVersionTagResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct DeleteModelVersionTagRequest {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) key: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DeleteModelVersionTagResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct CreateMultipartUploadRequest {
+    pub(crate) path: String,
+    pub(crate) num_parts: u64,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CreateMultipartUploadResponse {
+    pub(crate) upload_id: String,
+    #[serde(default)]
+    pub(crate) credentials: Vec<MultipartUploadCredential>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct MultipartUploadCredential {
+    pub(crate) url: String,
+    pub(crate) part_number: u64,
+    #[serde(default)]
+    pub(crate) headers: HashMap<String, String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct MultipartUploadPart {
+    pub(crate) part_number: u64,
+    pub(crate) etag: String,
+    pub(crate) url: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct CompleteMultipartUploadRequest {
+    pub(crate) path: String,
+    pub(crate) upload_id: String,
+    pub(crate) parts: Vec<MultipartUploadPart>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CompleteMultipartUploadResponse {}
+
+#[derive(Serialize)]
+pub(crate) struct AbortMultipartUploadRequest {
+    pub(crate) path: String,
+    pub(crate) upload_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AbortMultipartUploadResponse {}
@@ -1,64 +1,77 @@
-use std::{fmt::Display, thread, time::Duration};
+use std::{collections::VecDeque, thread, time::Duration};
 
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::error::{Error, ErrorCode};
 
 #[derive(Deserialize, Debug)]
-pub struct ErrorResponse {
-    error_code: String,
+pub(crate) struct ErrorResponse {
+    error_code: ErrorCode,
     message: String,
 }
 
-impl Display for ErrorResponse {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&format!("error code: {}\n", self.error_code))?;
-        f.write_str(&format!("message: {}", self.message))?;
-        Ok(())
-    }
-}
-
-impl std::error::Error for ErrorResponse {}
-
-pub(crate) fn check_for_error_response(response: &str) -> Result<(), ErrorResponse> {
+pub(crate) fn check_for_error_response(status: StatusCode, response: &str) -> Result<(), Error> {
     match serde_json::from_str::<ErrorResponse>(response) {
-        Ok(e) => Err(e),
+        Ok(e) => Err(Error::Server {
+            status,
+            error_code: e.error_code,
+            message: e.message,
+        }),
         Err(_) => Ok(()),
     }
 }
 
-pub(crate) fn checked_get_request<I: Serialize + ?Sized, O: DeserializeOwned>(
-    path: &str,
-    input: &I,
-) -> Result<O, Box<dyn std::error::Error>> {
-    let client = reqwest::blocking::Client::new();
-
-    let response = client
-        .get(path)
-        .json(input)
-        .send()?
-        .error_for_status()?
-        .text()?;
-
-    check_for_error_response(&response)?;
-
-    Ok(serde_json::from_str(&response)?)
+/// Iterator over the items of a paginated endpoint which requests the next page when the current one is used up.
+/// The iterator stops after the first error.
+pub(crate) struct Paginated<T, F>
+where
+    F: FnMut(Option<String>) -> Result<(Vec<T>, Option<String>), Error>,
+{
+    fetch_page: F,
+    items: VecDeque<T>,
+    next_page_token: Option<String>,
+    done: bool,
 }
 
-pub(crate) fn checked_post_request<I: Serialize + ?Sized, O: DeserializeOwned>(
-    path: &str,
-    input: &I,
-) -> Result<O, Box<dyn std::error::Error>> {
-    let client = reqwest::blocking::Client::new();
-
-    let response = client
-        .post(path)
-        .json(input)
-        .send()?
-        .error_for_status()?
-        .text()?;
+impl<T, F> Paginated<T, F>
+where
+    F: FnMut(Option<String>) -> Result<(Vec<T>, Option<String>), Error>,
+{
+    pub(crate) fn new(fetch_page: F) -> Self {
+        Self {
+            fetch_page,
+            items: VecDeque::new(),
+            next_page_token: None,
+            done: false,
+        }
+    }
+}
 
-    check_for_error_response(&response)?;
+impl<T, F> Iterator for Paginated<T, F>
+where
+    F: FnMut(Option<String>) -> Result<(Vec<T>, Option<String>), Error>,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // loop, because a page can be empty even though there are more pages
+        while self.items.is_empty() && !self.done {
+            match (self.fetch_page)(self.next_page_token.take()) {
+                Ok((items, next_page_token)) => {
+                    self.items.extend(items);
+                    self.next_page_token = next_page_token.filter(|token| !token.is_empty());
+                    self.done = self.next_page_token.is_none();
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
 
-    Ok(serde_json::from_str(&response)?)
+        self.items.pop_front().map(Ok)
+    }
 }
 
 pub fn retry<F, T, E>(function: F) -> Result<T, E>